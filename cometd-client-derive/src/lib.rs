@@ -0,0 +1,182 @@
+//! Derive macro for [cometd-client](https://docs.rs/cometd-client) messages.
+//!
+//! `#[derive(CometdMessage)]` is used on enum which variants are marked by
+//! `#[cometd(channel = "...")]` attribute. Received `data` is parsed into first variant
+//! which channel pattern matches message channel:
+//!
+//! ```rust,ignore
+//! use cometd_client::types::CometdMessage;
+//! use serde::Deserialize;
+//!
+//! #[derive(Debug, Deserialize)]
+//! struct Order {
+//!     id: u64,
+//! }
+//!
+//! #[derive(Debug, CometdMessage)]
+//! enum Msg {
+//!     #[cometd(channel = "/orders/**")]
+//!     Order(Order),
+//!     #[cometd(channel = "/heartbeat")]
+//!     Heartbeat,
+//! }
+//! ```
+//!
+//! Variants must be newtype (`data` is parsed into field) or unit (`data` is ignored and can be absent).
+//! Channel patterns are validated in compile time by the same rules as `ChannelId` has.
+
+#![deny(unsafe_code)]
+
+use proc_macro::TokenStream;
+use proc_macro2::TokenStream as TokenStream2;
+use quote::{quote, quote_spanned};
+use syn::{parse_macro_input, Data, DeriveInput, Error, Fields, LitStr, Result, Variant};
+
+/// Implement `CometdMessage` for enum with `#[cometd(channel = "...")]` variants.
+#[proc_macro_derive(CometdMessage, attributes(cometd))]
+pub fn derive_cometd_message(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+
+    expand(&input)
+        .unwrap_or_else(Error::into_compile_error)
+        .into()
+}
+
+fn expand(input: &DeriveInput) -> Result<TokenStream2> {
+    let Data::Enum(data) = &input.data else {
+        return Err(Error::new_spanned(
+            input,
+            "`CometdMessage` can be derived only for enums",
+        ));
+    };
+
+    let name = &input.ident;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+    let variants = data
+        .variants
+        .iter()
+        .map(|variant| expand_variant(name, variant))
+        .collect::<Result<Vec<_>>>()?;
+    let checks = variants.iter().map(|variant| &variant.check);
+    let data_arms = variants.iter().map(|variant| &variant.data_arm);
+    let empty_arms = variants.iter().map(|variant| &variant.empty_arm);
+    let name_str = name.to_string();
+
+    Ok(quote! {
+        #(#checks)*
+
+        impl #impl_generics ::cometd_client::types::CometdMessage for #name #ty_generics #where_clause {
+            #[allow(unused_variables)]
+            fn from_data(
+                channel: ::core::option::Option<&::cometd_client::types::ChannelId>,
+                data: ::cometd_client::types::__private::JsonValue,
+            ) -> ::cometd_client::types::CometdResult<Self> {
+                let channel = channel.map_or("", ::cometd_client::types::ChannelId::as_str);
+
+                #(#data_arms)*
+
+                ::core::result::Result::Err(::cometd_client::types::CometdError::UnmatchedChannel(
+                    ::core::convert::From::from(channel),
+                    #name_str,
+                ))
+            }
+
+            fn from_empty(
+                channel: ::core::option::Option<&::cometd_client::types::ChannelId>,
+            ) -> ::cometd_client::types::CometdResult<::core::option::Option<Self>> {
+                let channel = channel.map_or("", ::cometd_client::types::ChannelId::as_str);
+
+                #(#empty_arms)*
+
+                ::core::result::Result::Err(::cometd_client::types::CometdError::UnmatchedChannel(
+                    ::core::convert::From::from(channel),
+                    #name_str,
+                ))
+            }
+        }
+    })
+}
+
+struct VariantExpansion {
+    /// Compile time check of channel pattern.
+    check: TokenStream2,
+    /// Parse variant from `data`.
+    data_arm: TokenStream2,
+    /// Build variant without `data`, newtype variants are left empty.
+    empty_arm: TokenStream2,
+}
+
+fn expand_variant(name: &syn::Ident, variant: &Variant) -> Result<VariantExpansion> {
+    let ident = &variant.ident;
+    let channel = channel_attr(variant)?;
+
+    let (value, empty) = match &variant.fields {
+        Fields::Unit => (
+            quote!(#name::#ident),
+            quote!(::core::option::Option::Some(#name::#ident)),
+        ),
+        Fields::Unnamed(fields) if fields.unnamed.len() == 1 => (
+            quote!(#name::#ident(::cometd_client::types::__private::parse_data(data)?)),
+            quote!(::core::option::Option::None),
+        ),
+        _ => {
+            return Err(Error::new_spanned(
+                variant,
+                "`CometdMessage` variant must be unit or newtype",
+            ))
+        }
+    };
+    let matches = quote! {
+        ::cometd_client::types::ChannelPattern::new(#channel).matches(channel)
+    };
+
+    Ok(VariantExpansion {
+        // same validation as `ChannelId` has, error points to attribute
+        check: quote_spanned! {channel.span()=>
+            const _: () = if let ::core::result::Result::Err(reason) =
+                ::cometd_client::types::__private::validate_channel(#channel)
+            {
+                ::core::panic!("{}", reason);
+            };
+        },
+        data_arm: quote! {
+            if #matches {
+                return ::core::result::Result::Ok(#value);
+            }
+        },
+        empty_arm: quote! {
+            if #matches {
+                return ::core::result::Result::Ok(#empty);
+            }
+        },
+    })
+}
+
+fn channel_attr(variant: &Variant) -> Result<LitStr> {
+    let mut channel = None;
+
+    for attr in variant
+        .attrs
+        .iter()
+        .filter(|attr| attr.path().is_ident("cometd"))
+    {
+        attr.parse_nested_meta(|meta| {
+            if !meta.path.is_ident("channel") {
+                return Err(meta.error("unknown `cometd` attribute, expected `channel`"));
+            }
+            if channel.is_some() {
+                return Err(meta.error("duplicated `channel` attribute"));
+            }
+
+            channel = Some(meta.value()?.parse::<LitStr>()?);
+            Ok(())
+        })?;
+    }
+
+    channel.ok_or_else(|| {
+        Error::new_spanned(
+            &variant.ident,
+            "missing `#[cometd(channel = \"...\")]` attribute",
+        )
+    })
+}
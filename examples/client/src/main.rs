@@ -10,7 +10,13 @@ async fn main() {
         .unwrap();
     let mut rx = client.rx();
 
-    client.subscribe(&["/topic0", "/topic1"]).await;
+    for (channel, result) in client
+        .subscribe_batch(&["/topic0", "/topic1"])
+        .await
+        .unwrap()
+    {
+        println!("subscribe to `{channel}`: `{result:?}`.");
+    }
 
     for _ in 0..3 {
         let response = rx.recv().await.unwrap();
@@ -1,47 +1,89 @@
+mod advice;
 mod builder;
+mod call;
 mod connect;
+mod connection_types;
 mod disconnect;
+mod endpoints;
+mod extensions;
+mod handles;
 mod handshake;
+mod publish;
+mod routes;
+mod snapshot;
 mod subscribe;
+mod unsubscribe;
 
 pub use builder::*;
 
-use crate::{ext::CookieJarExt as _, types::*, ArcSwapOptionExt};
-use arc_swap::ArcSwapOption;
+pub(crate) use {endpoints::*, handles::*, routes::*};
+
+use crate::{
+    ext::CookieJarExt as _,
+    types::{
+        extension::{AckExtension, TimesyncExtension},
+        router::{Handler, RouteHandle},
+        *,
+    },
+    ArcSwapOptionExt,
+};
+use arc_swap::{ArcSwap, ArcSwapOption};
 use cookie::{Cookie, CookieJar};
 use core::{
     sync::atomic::{AtomicUsize, Ordering},
     time::Duration,
 };
-use hyper::{client::HttpConnector, header::SET_COOKIE, http::HeaderValue, Client, HeaderMap, Uri};
-use serde::Serialize;
-use serde_json::json;
-use std::sync::{Mutex, PoisonError};
+use hyper::{client::HttpConnector, header::SET_COOKIE, http::HeaderValue, Client, HeaderMap};
+use serde::{de::DeserializeOwned, Serialize};
+use serde_json::Value as JsonValue;
+use std::{
+    collections::{BTreeSet, HashMap},
+    sync::{Arc, Mutex, MutexGuard, PoisonError},
+};
+use tokio::sync::{mpsc, oneshot};
+use url::Url;
 
 /// A cometd Client.
 #[derive(Debug)]
 pub struct CometdClient<Msg> {
+    inner: Arc<CometdClientInner>,
     cmd_tx: CmdSender,
     inactive_event_rx: InactiveEventReceiver<Msg>,
 }
 
 #[derive(Debug)]
 pub(crate) struct CometdClientInner {
-    handshake_endpoint: Uri,
-    subscribe_endpoint: Uri,
-    connect_endpoint: Uri,
-    disconnect_endpoint: Uri,
+    endpoint_paths: EndpointPaths,
+    endpoints: Mutex<Vec<Arc<Endpoints>>>,
+    endpoint: ArcSwap<Endpoints>,
     timeout: Duration,
     interval: Duration,
-    number_of_retries: usize,
+    pub(crate) retry_policy: Box<dyn RetryPolicy>,
+    backoff: Backoff,
+    supervised: bool,
 
     id: AtomicUsize,
     pub(crate) access_token: ArcSwapOption<Box<dyn AccessToken>>,
     cookies: Mutex<CookieJar>,
     pub(crate) cookies_string_cache: ArcSwapOption<Box<str>>,
-    client_id: ArcSwapOption<Box<str>>,
+    pub(crate) client_id: ArcSwapOption<Box<str>>,
+    advice: Mutex<Advice>,
+    subscriptions: Mutex<BTreeSet<ChannelId>>,
     pub(crate) http_client: Client<HttpConnector>,
-    pub(crate) request_timeout: Duration,
+    request_timeout: Duration,
+    connection_types: Box<[ConnectionType]>,
+    available_connection_types: Mutex<Vec<ConnectionType>>,
+    extensions: Box<[Arc<dyn Extension>]>,
+    ack: Option<Arc<AckExtension>>,
+    timesync: Option<Arc<TimesyncExtension>>,
+    pub(crate) pushed_tx: mpsc::UnboundedSender<Vec<Message>>,
+    subscription_channel_capacity: usize,
+    calls: Mutex<HashMap<String, oneshot::Sender<Message>>>,
+    routes: Mutex<Vec<Route>>,
+    subscription_refs: Mutex<HashMap<ChannelId, SubscriptionRefs>>,
+    pub(crate) notification_tx: NotificationSender,
+    #[cfg(feature = "websocket")]
+    pub(crate) websocket: tokio::sync::Mutex<Option<Arc<crate::common::WebSocket>>>,
 }
 
 impl<Msg> CometdClient<Msg> {
@@ -53,8 +95,7 @@ impl<Msg> CometdClient<Msg> {
     /// # async fn _fun() {
     /// #   let client = CometdClientBuilder::new(&"http://[::1]:1025/".parse().unwrap()).build::<()>().unwrap();
     ///     let mut event_rx = client.rx();
-    ///     
-    ///     client.subscribe(&["/topic0"]).await;
+    ///     let _ = client.subscribe_batch(&["/topic0"]).await;
     ///
     ///     while let Some(event) = event_rx.recv().await {
     ///         println!("Got cometd client event: `{event:?}`.");
@@ -66,26 +107,499 @@ impl<Msg> CometdClient<Msg> {
         CometdEventReceiver(self.inactive_event_rx.activate_cloned())
     }
 
-    /// Ask client command loop to send subscribe request.
+    /// Return event channel receiver which yields only messages from channels
+    /// matching `pattern` (`*` matches one segment, `**` matches many), errors are skipped.
     ///
     /// # Example
     /// ```rust,no_run
     /// # use cometd_client::{CometdClientBuilder, types::CometdResult};
     /// # async fn _fun() {
     /// #   let client = CometdClientBuilder::new(&"http://[::1]:1025/".parse().unwrap()).build::<()>().unwrap();
-    ///     client.subscribe(&["/topic0", "/topic1"]).await;
+    ///     let mut orders_rx = client.rx_matching("/orders/**");
+    ///
+    ///     let _ = client.subscribe_batch(&["/orders/**", "/prices/*"]).await;
+    ///
+    ///     while let Some(data) = orders_rx.recv().await {
+    ///         println!("Got order from `{:?}`: `{:?}`.", data.channel, data.message);
+    ///     }
     /// # }
     /// ```
     #[inline(always)]
-    pub async fn subscribe(&self, subscriptions: &[impl Serialize + Send + Sync]) {
-        let _ = self
-            .cmd_tx
-            .send(Command::Subscribe(json!(subscriptions)))
-            .await;
+    pub fn rx_matching(&self, pattern: impl Into<ChannelPattern>) -> CometdMatchingReceiver<Msg> {
+        CometdMatchingReceiver::new(self.rx(), pattern.into())
+    }
+
+    /// Subscribe to `channel` and return handle which yields its messages.
+    ///
+    /// Handles to same channel share one server subscription:
+    /// subscribe request is sent only if client isn't subscribed yet,
+    /// unsubscribe request is sent when the last handle is dropped.
+    /// Messages are still sent to event channel too, but every handle parses them by itself.
+    /// Handle keeps up to [`CometdClientBuilder::subscription_channel_capacity`] unread messages,
+    /// so it must be read, otherwise use [`CometdClient::subscribe_batch`].
+    ///
+    /// Return error if channel name is invalid or server denied subscription.
+    ///
+    /// # Example
+    /// ```rust,no_run
+    /// # use cometd_client::{CometdClientBuilder, types::CometdResult};
+    /// # async fn _fun() -> CometdResult<()> {
+    /// #   let client = CometdClientBuilder::new(&"http://[::1]:1025/".parse().unwrap()).build::<()>().unwrap();
+    ///     let mut topic = client.subscribe("/topic0").await?;
+    ///
+    ///     while let Some(data) = topic.recv().await {
+    ///         println!("Got message from `{}`: `{:?}`.", topic.channel(), data?.message);
+    ///     }
+    ///     // `topic` is dropped here, so client unsubscribes from `/topic0`
+    /// #   Ok(())
+    /// # }
+    /// ```
+    #[inline]
+    pub async fn subscribe(&self, channel: impl AsRef<str>) -> CometdResult<Subscription<Msg>> {
+        let channel = ChannelId::new(channel.as_ref())?;
+        let rx = self.inner.add_route(channel.clone().into(), false);
+        let guard = self.acquire_subscription(channel).await?;
+
+        Ok(Subscription::new(rx, guard))
+    }
+
+    /// Ask client command loop to send subscribe request and wait for server reply.
+    ///
+    /// Unlike [`CometdClient::subscribe`] it doesn't return handles: messages are sent
+    /// to event channel only and channels stay subscribed until [`CometdClient::unsubscribe`].
+    ///
+    /// Return error if request wasn't done at all or some channel name is invalid,
+    /// otherwise return server verdict for every channel.
+    ///
+    /// # Example
+    /// ```rust,no_run
+    /// # use cometd_client::{CometdClientBuilder, types::CometdResult};
+    /// # async fn _fun() -> CometdResult<()> {
+    /// #   let client = CometdClientBuilder::new(&"http://[::1]:1025/".parse().unwrap()).build::<()>().unwrap();
+    ///     for (channel, result) in client.subscribe_batch(&["/topic0", "/topic1"]).await? {
+    ///         if let Err(error) = result {
+    ///             eprintln!("Can't subscribe to `{channel}`: `{error}`.");
+    ///         }
+    ///     }
+    /// #   Ok(())
+    /// # }
+    /// ```
+    #[inline]
+    pub async fn subscribe_batch(
+        &self,
+        subscriptions: &[impl AsRef<str>],
+    ) -> CometdResult<Vec<(ChannelId, CometdResult<()>)>> {
+        let subscriptions = to_channels(subscriptions)?;
+
+        self.send_command(|reply_tx| Command::Subscribe {
+            subscriptions,
+            reply_tx,
+        })
+        .await
+    }
+
+    /// Ask client command loop to send unsubscribe request and wait for server reply.
+    ///
+    /// Channels which still have subscription handles (look at [`CometdClient::subscribe`])
+    /// aren't sent to server and stay subscribed until the last handle is dropped.
+    ///
+    /// Return error if request wasn't done at all or some channel name is invalid,
+    /// otherwise return server verdict for every channel.
+    ///
+    /// # Example
+    /// ```rust,no_run
+    /// # use cometd_client::{CometdClientBuilder, types::CometdResult};
+    /// # async fn _fun() -> CometdResult<()> {
+    /// #   let client = CometdClientBuilder::new(&"http://[::1]:1025/".parse().unwrap()).build::<()>().unwrap();
+    ///     client.subscribe_batch(&["/topic0", "/topic1"]).await?;
+    ///     client.unsubscribe(&["/topic1"]).await?;
+    /// #   Ok(())
+    /// # }
+    /// ```
+    #[inline]
+    pub async fn unsubscribe(
+        &self,
+        subscriptions: &[impl AsRef<str>],
+    ) -> CometdResult<Vec<(ChannelId, CometdResult<()>)>> {
+        let subscriptions = to_channels(subscriptions)?;
+
+        self.send_command(|reply_tx| Command::Unsubscribe {
+            subscriptions,
+            reply_tx,
+        })
+        .await
+    }
+
+    /// Subscribe to `channel` and return handle which yields its messages parsed as `T`.
+    ///
+    /// Every typed subscription parses messages by itself, so channels can carry
+    /// different payloads. Messages routed to typed subscriptions aren't sent
+    /// to event channel and aren't parsed as `Msg`.
+    /// Server subscription is shared with other handles like in [`CometdClient::subscribe`].
+    ///
+    /// Return error if channel name is invalid or server denied subscription.
+    ///
+    /// # Example
+    /// ```rust,no_run
+    /// # use cometd_client::{CometdClientBuilder, types::CometdResult};
+    /// # async fn _fun() -> CometdResult<()> {
+    /// #   let client = CometdClientBuilder::new(&"http://[::1]:1025/".parse().unwrap()).build::<()>().unwrap();
+    ///     #[derive(Debug, serde::Deserialize)]
+    ///     struct Price {
+    ///         symbol: String,
+    ///         value: f64,
+    ///     }
+    ///
+    ///     let mut prices = client.subscribe_typed::<Price>("/prices/*").await?;
+    ///
+    ///     while let Some(price) = prices.recv().await {
+    ///         println!("Got price: `{:?}`.", price?.message);
+    ///     }
+    /// #   Ok(())
+    /// # }
+    /// ```
+    pub async fn subscribe_typed<T: CometdMessage>(
+        &self,
+        channel: impl AsRef<str>,
+    ) -> CometdResult<Subscription<T>> {
+        let channel = ChannelId::new(channel.as_ref())?;
+        let rx = self.inner.add_route(channel.clone().into(), true);
+        let guard = self.acquire_subscription(channel).await?;
+
+        Ok(Subscription::new(rx, guard))
+    }
+
+    /// Subscribe to `channel` and call `handler` for every its message,
+    /// handler arguments are extracted from message (look at [`crate::types::router::FromData`]).
+    ///
+    /// Handlers are called one by one in order of messages,
+    /// use [`CometdClient::route_with_concurrency`] to run them concurrently.
+    /// Handler errors and panics are sent to event channel as [`CometdError::Handler`]
+    /// and [`CometdError::HandlerPanic`] and don't stop route.
+    /// Routed messages aren't sent to event channel.
+    ///
+    /// Return error if channel name is invalid or server denied subscription.
+    ///
+    /// # Example
+    /// ```rust,no_run
+    /// # use cometd_client::{CometdClientBuilder, types::{router::Json, ChannelId, CometdResult}};
+    /// # async fn _fun() -> CometdResult<()> {
+    /// #   let client = CometdClientBuilder::new(&"http://[::1]:1025/".parse().unwrap()).build::<()>().unwrap();
+    ///     #[derive(Debug, serde::Deserialize)]
+    ///     struct Order {
+    ///         id: u64,
+    ///     }
+    ///
+    ///     let _orders = client
+    ///         .route("/orders/*", |Json(order): Json<Order>, channel: ChannelId| async move {
+    ///             println!("Got order `{}` from `{channel}`.", order.id);
+    ///         })
+    ///         .await?;
+    /// #   Ok(())
+    /// # }
+    /// ```
+    #[inline]
+    pub async fn route<Args>(
+        &self,
+        channel: impl AsRef<str>,
+        handler: impl Handler<Args>,
+    ) -> CometdResult<RouteHandle> {
+        self.route_with_concurrency(channel, 1, handler).await
+    }
+
+    /// Same as [`CometdClient::route`], but up to `concurrency` handlers can run at the same time,
+    /// so messages can be handled out of order.
+    ///
+    /// # Example
+    /// ```rust,no_run
+    /// # use cometd_client::{CometdClientBuilder, types::{router::Json, CometdResult}};
+    /// # async fn _fun() -> CometdResult<()> {
+    /// #   let client = CometdClientBuilder::new(&"http://[::1]:1025/".parse().unwrap()).build::<()>().unwrap();
+    ///     let _prices = client
+    ///         .route_with_concurrency("/prices/**", 8, |Json(price): Json<f64>| async move {
+    ///             if price < 0.0 {
+    ///                 return Err(format!("negative price `{price}`"));
+    ///             }
+    ///             Ok(())
+    ///         })
+    ///         .await?;
+    /// #   Ok(())
+    /// # }
+    /// ```
+    pub async fn route_with_concurrency<Args>(
+        &self,
+        channel: impl AsRef<str>,
+        concurrency: usize,
+        handler: impl Handler<Args>,
+    ) -> CometdResult<RouteHandle> {
+        let subscription = self.subscribe_typed::<JsonValue>(channel).await?;
+
+        Ok(RouteHandle::spawn(
+            Arc::clone(&self.inner),
+            subscription,
+            concurrency,
+            handler,
+        ))
+    }
+
+    /// Return base url of cometd server which is currently used.
+    ///
+    /// # Example
+    /// ```rust,no_run
+    /// # use cometd_client::{CometdClientBuilder, types::CometdResult};
+    /// # async fn _fun() {
+    /// #   let client = CometdClientBuilder::new(&"http://[::1]:1025/".parse().unwrap()).build::<()>().unwrap();
+    ///     println!("Current endpoint: `{}`.", client.endpoint());
+    /// # }
+    /// ```
+    #[inline]
+    pub fn endpoint(&self) -> Url {
+        self.inner.endpoint().base_url.clone()
+    }
+
+    /// Return connection type which is currently used.
+    ///
+    /// # Example
+    /// ```rust,no_run
+    /// # use cometd_client::{CometdClientBuilder, types::CometdResult};
+    /// # async fn _fun() {
+    /// #   let client = CometdClientBuilder::new(&"http://[::1]:1025/".parse().unwrap()).build::<()>().unwrap();
+    ///     println!("Current connection type: `{:?}`.", client.connection_type());
+    /// # }
+    /// ```
+    #[inline]
+    pub fn connection_type(&self) -> ConnectionType {
+        self.inner.connection_type()
+    }
+
+    /// Return acknowledgement extension if it was enabled through
+    /// [`CometdClientBuilder::ack_extension`].
+    ///
+    /// # Example
+    /// ```rust,no_run
+    /// # use cometd_client::CometdClientBuilder;
+    /// # async fn _fun() {
+    /// #   let client = CometdClientBuilder::new(&"http://[::1]:1025/".parse().unwrap()).ack_extension().build::<()>().unwrap();
+    ///     if let Some(ack) = client.ack() {
+    ///         println!("Last received batch: `{}`.", ack.batch());
+    ///     }
+    /// # }
+    /// ```
+    #[inline]
+    pub fn ack(&self) -> Option<&AckExtension> {
+        self.inner.ack.as_deref()
+    }
+
+    /// Return timesync extension if it was enabled through
+    /// [`CometdClientBuilder::timesync_extension`].
+    ///
+    /// # Example
+    /// ```rust,no_run
+    /// # use cometd_client::{CometdClientBuilder, types::CometdResult};
+    /// # async fn _fun() {
+    /// #   let client = CometdClientBuilder::new(&"http://[::1]:1025/".parse().unwrap()).timesync_extension().build::<()>().unwrap();
+    ///     if let Some(timesync) = client.timesync() {
+    ///         println!("Server clock offset: `{}ms`, lag: `{:?}`.", timesync.offset(), timesync.lag());
+    ///     }
+    /// # }
+    /// ```
+    #[inline]
+    pub fn timesync(&self) -> Option<&TimesyncExtension> {
+        self.inner.timesync.as_deref()
+    }
+
+    /// Return channels which client is subscribed to.
+    ///
+    /// # Example
+    /// ```rust,no_run
+    /// # use cometd_client::{CometdClientBuilder, types::CometdResult};
+    /// # async fn _fun() {
+    /// #   let client = CometdClientBuilder::new(&"http://[::1]:1025/".parse().unwrap()).build::<()>().unwrap();
+    ///     let _ = client.subscribe_batch(&["/topic0"]).await;
+    ///
+    ///     println!("Active subscriptions: `{:?}`.", client.subscriptions());
+    /// # }
+    /// ```
+    #[inline]
+    pub fn subscriptions(&self) -> Vec<ChannelId> {
+        self.inner.subscriptions().iter().cloned().collect()
+    }
+
+    /// Ask client command loop to publish `data` to `channel` and wait for server reply.
+    ///
+    /// Return [`CometdError::InvalidChannel`] if `channel` is invalid, wildcard or meta one.
+    ///
+    /// # Example
+    /// ```rust,no_run
+    /// # use cometd_client::{CometdClientBuilder, types::CometdResult};
+    /// # async fn _fun() -> CometdResult<()> {
+    /// #   let client = CometdClientBuilder::new(&"http://[::1]:1025/".parse().unwrap()).build::<()>().unwrap();
+    ///     client.publish("/topic0", serde_json::json!({ "msg": "Hello!" })).await?;
+    /// #   Ok(())
+    /// # }
+    /// ```
+    #[inline]
+    pub async fn publish(
+        &self,
+        channel: impl AsRef<str>,
+        data: impl Serialize,
+    ) -> CometdResult<()> {
+        let channel = ChannelId::new(channel.as_ref())?.check_publishable()?;
+        let data = serde_json::to_value(data)
+            .map_err(|error| CometdError::SerializeData(ErrorKind::Publish, error))?;
+
+        self.send_command(|reply_tx| Command::Publish {
+            channel,
+            data,
+            reply_tx,
+        })
+        .await
+    }
+
+    /// Send `request` to service `channel` and wait for server reply with same message id.
+    ///
+    /// Reply isn't sent to event channel, its data is parsed as `Resp`.
+    /// Return [`CometdError::RequestTimeout`] if reply wasn't got in `timeout`
+    /// and [`CometdError::InvalidChannel`] if `channel` isn't service one.
+    ///
+    /// # Example
+    /// ```rust,no_run
+    /// # use cometd_client::{CometdClientBuilder, types::CometdResult};
+    /// # use core::time::Duration;
+    /// # async fn _fun() -> CometdResult<()> {
+    /// #   let client = CometdClientBuilder::new(&"http://[::1]:1025/".parse().unwrap()).build::<()>().unwrap();
+    ///     let sum: i64 = client
+    ///         .call("/service/sum", [1, 2, 3], Duration::from_secs(5))
+    ///         .await?;
+    /// #   Ok(())
+    /// # }
+    /// ```
+    pub async fn call<Req, Resp>(
+        &self,
+        channel: impl AsRef<str>,
+        request: Req,
+        timeout: Duration,
+    ) -> CometdResult<Resp>
+    where
+        Req: Serialize,
+        Resp: DeserializeOwned,
+    {
+        const KIND: ErrorKind = ErrorKind::Call;
+
+        let channel = ChannelId::new(channel.as_ref())?.check_publishable()?;
+        if !channel.is_service() {
+            return Err(CometdError::InvalidChannel(
+                channel.as_str().into(),
+                "remote calls are done through service channels",
+            ));
+        }
+        let data = serde_json::to_value(request)
+            .map_err(|error| CometdError::SerializeData(KIND, error))?;
+        let id = self.inner.next_id();
+        let reply_rx = self.inner.register_call(id.clone());
+
+        let result = tokio::time::timeout(timeout, async {
+            let result = self
+                .send_command(|reply_tx| Command::Call {
+                    id: id.clone(),
+                    channel,
+                    data,
+                    reply_tx,
+                })
+                .await?;
+
+            match result {
+                Some(data) => Ok(data),
+                None => reply_rx
+                    .await
+                    .map_err(|_| CometdError::ClientClosed)
+                    .and_then(call::call_result),
+            }
+        })
+        .await
+        .unwrap_or(Err(CometdError::RequestTimeout(KIND)));
+        self.inner.forget_call(&id);
+
+        serde_json::from_value(result?).map_err(|error| CometdError::ParseBody(KIND, error))
+    }
+
+    /// Return current session state, which can be used to resume session
+    /// through [`CometdClientBuilder::resume`].
+    ///
+    /// Session is ended by disconnect request when client is dropped,
+    /// so use [`CometdClient::into_snapshot`] to stop client without it.
+    #[inline]
+    pub fn snapshot(&self) -> SessionSnapshot {
+        self.inner.snapshot()
+    }
+
+    /// Stop client without disconnect request and return session state,
+    /// which can be used to resume session through [`CometdClientBuilder::resume`].
+    ///
+    /// # Example
+    /// ```rust,no_run
+    /// # use cometd_client::{CometdClientBuilder, types::CometdResult};
+    /// # async fn _fun() {
+    /// #   let client = CometdClientBuilder::new(&"http://[::1]:1025/".parse().unwrap()).build::<()>().unwrap();
+    ///     let snapshot = client.into_snapshot();
+    ///     std::fs::write("session.json", serde_json::to_vec(&snapshot).unwrap()).unwrap();
+    /// # }
+    /// ```
+    #[inline]
+    pub fn into_snapshot(self) -> SessionSnapshot {
+        let snapshot = self.inner.snapshot();
+        // client task doesn't send disconnect request without client id
+        self.inner.client_id.store(None);
+
+        snapshot
+    }
+
+    /// Ask client task to take subscription handle and subscribe to `channel`
+    /// if client isn't subscribed yet.
+    /// Receiver must be created before, so no message is lost.
+    #[inline]
+    async fn acquire_subscription(&self, channel: ChannelId) -> CometdResult<SubscriptionGuard> {
+        let (reply_tx, reply_rx) = oneshot::channel();
+
+        self.cmd_tx
+            .send(Command::Acquire {
+                channel: channel.clone(),
+                reply_tx,
+            })
+            .await
+            .map_err(|_| CometdError::ClientClosed)?;
+        // client task takes handle in any case, so it's released
+        // even if subscription fails or caller is cancelled
+        let guard = SubscriptionGuard::new(Arc::clone(&self.inner), channel);
+        reply_rx.await.map_err(|_| CometdError::ClientClosed)??;
+
+        Ok(guard)
+    }
+
+    #[inline]
+    async fn send_command<T>(
+        &self,
+        make_command: impl FnOnce(ReplySender<T>) -> Command,
+    ) -> CometdResult<T> {
+        let (reply_tx, reply_rx) = oneshot::channel();
+
+        self.cmd_tx
+            .send(make_command(reply_tx))
+            .await
+            .map_err(|_| CometdError::ClientClosed)?;
+
+        reply_rx.await.map_err(|_| CometdError::ClientClosed)?
     }
 }
 
 impl CometdClientInner {
+    #[inline(always)]
+    pub(crate) fn subscriptions(&self) -> MutexGuard<'_, BTreeSet<ChannelId>> {
+        self.subscriptions
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+    }
+
     #[inline(always)]
     pub(crate) fn next_id(&self) -> String {
         self.id.fetch_add(1, Ordering::Relaxed).to_string()
@@ -114,3 +628,12 @@ impl CometdClientInner {
         }
     }
 }
+
+#[inline(always)]
+fn to_channels(subscriptions: &[impl AsRef<str>]) -> CometdResult<Box<[ChannelId]>> {
+    subscriptions
+        .iter()
+        .map(AsRef::as_ref)
+        .map(ChannelId::new)
+        .collect()
+}
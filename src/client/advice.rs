@@ -0,0 +1,60 @@
+use crate::{
+    types::{Advice, ErrorKind, Reconnect},
+    CometdClientInner,
+};
+use core::time::Duration;
+use std::sync::PoisonError;
+
+impl CometdClientInner {
+    /// Remember advice from server reply.
+    #[inline]
+    pub(crate) fn update_advice(&self, advice: Option<&Advice>) {
+        if let Some(hosts) = advice.and_then(|advice| advice.hosts.as_deref()) {
+            self.add_hosts(hosts);
+        }
+
+        let mut current = self.advice.lock().unwrap_or_else(PoisonError::into_inner);
+        match advice {
+            Some(advice) => current.update(advice),
+            None => current.reconnect = None,
+        }
+    }
+
+    #[inline]
+    pub(crate) fn advice(&self) -> Advice {
+        self.advice
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .clone()
+    }
+
+    /// Return how long to wait before next connect request.
+    #[inline]
+    pub(crate) fn connect_interval(&self) -> Duration {
+        self.advice()
+            .interval
+            .map_or(self.interval, Duration::from_millis)
+    }
+
+    /// Return request timeout, connect requests are additionally prolonged by server timeout.
+    #[inline]
+    pub(crate) fn request_timeout(&self, kind: ErrorKind) -> Duration {
+        match kind {
+            ErrorKind::Connect => {
+                let server_timeout = self
+                    .advice()
+                    .timeout
+                    .map_or(self.timeout, Duration::from_millis);
+
+                self.request_timeout.saturating_add(server_timeout)
+            }
+            _ => self.request_timeout,
+        }
+    }
+
+    /// Return `true` if server advised to not reconnect.
+    #[inline]
+    pub(crate) fn reconnect_forbidden(&self) -> bool {
+        self.advice().reconnect == Some(Reconnect::None)
+    }
+}
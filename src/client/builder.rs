@@ -3,55 +3,109 @@ mod client_task;
 use crate::{
     consts::*,
     ext::CookieJarExt,
-    types::{AccessToken, CometdResult},
-    CometdClient, CometdClientInner,
+    types::{
+        extension::{AckExtension, TimesyncExtension},
+        retry_policy::AdviceRetry,
+        AccessToken, Backoff, CometdError, CometdMessage, CometdResult, ConnectionType, Extension,
+        RetryPolicy, SessionSnapshot,
+    },
+    CometdClient, CometdClientInner, EndpointPaths, Endpoints, SubscriptionRefs,
 };
-use arc_swap::ArcSwapOption;
+use arc_swap::{ArcSwap, ArcSwapOption};
 use async_broadcast::broadcast;
 use cookie::{Cookie, CookieJar};
-use core::time::Duration;
+use core::{slice, time::Duration};
 use hyper::Client;
-use serde::de::DeserializeOwned;
-use std::{borrow::Cow, sync::Mutex};
+use std::{
+    borrow::Cow,
+    sync::{Arc, Mutex},
+};
 use tokio::sync::mpsc;
 use url::Url;
 
 /// A builder to construct `CometdClient`.
 #[derive(Debug)]
-pub struct CometdClientBuilder<'a, 'b, 'c, 'd, 'e> {
-    endpoint: &'a Url,
+pub struct CometdClientBuilder<'a, 'b, 'c, 'd, 'e, 'f, 'g> {
+    endpoints: &'a [Url],
     handshake_base_path: &'b str,
     subscribe_base_path: &'c str,
     connect_base_path: &'d str,
     disconnect_base_path: &'e str,
+    publish_base_path: &'f str,
     timeout: Option<Duration>,
     interval: Option<Duration>,
     access_token: Option<Box<dyn AccessToken>>,
     cookies: Option<CookieJar>,
     commands_channel_capacity: usize,
     events_channel_capacity: usize,
+    subscription_channel_capacity: usize,
     number_of_retries: usize,
+    retry_policy: Option<Box<dyn RetryPolicy>>,
+    backoff: Backoff,
+    supervised: bool,
     request_timeout: Duration,
+    connection_types: &'g [ConnectionType],
+    extensions: Vec<Arc<dyn Extension>>,
+    ack: Option<Arc<AckExtension>>,
+    timesync: Option<Arc<TimesyncExtension>>,
+    snapshot: Option<SessionSnapshot>,
 }
 
-impl<'a, 'b, 'c, 'd, 'e> CometdClientBuilder<'a, 'b, 'c, 'd, 'e> {
+impl<'a, 'b, 'c, 'd, 'e, 'f, 'g> CometdClientBuilder<'a, 'b, 'c, 'd, 'e, 'f, 'g> {
     /// Construct a new `ClientBuilder`.
     #[inline(always)]
     pub fn new(endpoint: &'a Url) -> Self {
+        Self::with_endpoints(slice::from_ref(endpoint))
+    }
+
+    /// Construct a new `ClientBuilder` with several cometd servers.
+    /// Client starts from first one and switches to next one on transport errors
+    /// or when server advises handshake and `advice.hosts`, if failed request is going to be redone.
+    /// Servers from `advice.hosts` are added to this list.
+    ///
+    /// Whether failed request will be redone is decided by retry policy,
+    /// so consider to use [`crate::types::retry_policy::TransportRetry`]
+    /// or [`CometdClientBuilder::supervised`].
+    ///
+    /// # Example
+    /// ```rust,no_run
+    /// # use cometd_client::{CometdClient, CometdClientBuilder};
+    /// # let _ = || -> cometd_client::types::CometdResult<_> {
+    /// let endpoints = [
+    ///     "http://[::1]:1025/notifications/".parse()?,
+    ///     "http://[::1]:1026/notifications/".parse()?,
+    /// ];
+    /// let client = CometdClientBuilder::with_endpoints(&endpoints)
+    ///     .build()?;
+    /// # let client: CometdClient<()> = client;
+    /// # Ok(()) };
+    /// ```
+    #[inline(always)]
+    pub fn with_endpoints(endpoints: &'a [Url]) -> Self {
         Self {
-            endpoint,
+            endpoints,
             handshake_base_path: "",
             subscribe_base_path: "",
             connect_base_path: "",
             disconnect_base_path: "",
+            publish_base_path: "",
             timeout: None,
             interval: None,
             access_token: None,
             cookies: None,
             commands_channel_capacity: DEFAULT_COMMAND_CHANNEL_CAPACITY,
             events_channel_capacity: DEFAULT_EVENT_CHANNEL_CAPACITY,
+            subscription_channel_capacity: DEFAULT_SUBSCRIPTION_CHANNEL_CAPACITY,
             number_of_retries: DEFAULT_NUMBER_OF_RETRIES,
+            retry_policy: None,
+            backoff: Backoff::default(),
+            supervised: false,
             request_timeout: DEFAULT_CLIENT_TIMEOUT,
+            connection_types: &[ConnectionType::LongPolling],
+            extensions: Vec::new(),
+            ack: None,
+            timesync: None,
+            snapshot: None,
         }
     }
 
@@ -71,70 +125,148 @@ impl<'a, 'b, 'c, 'd, 'e> CometdClientBuilder<'a, 'b, 'c, 'd, 'e> {
     #[inline(always)]
     pub fn build<Msg>(self) -> CometdResult<CometdClient<Msg>>
     where
-        Msg: DeserializeOwned + Send + Sync + 'static,
+        Msg: CometdMessage + Send + Sync + 'static,
     {
         let Self {
-            endpoint: base_url,
+            endpoints,
             handshake_base_path,
             subscribe_base_path,
             connect_base_path,
             disconnect_base_path,
+            publish_base_path,
             timeout,
             interval,
             access_token,
             cookies,
             commands_channel_capacity,
             events_channel_capacity,
+            subscription_channel_capacity,
             number_of_retries,
+            retry_policy,
+            backoff,
+            supervised,
             request_timeout,
+            connection_types,
+            extensions,
+            ack,
+            timesync,
+            snapshot,
         } = self;
 
-        let handshake_endpoint =
-            String::from(base_url.join(handshake_base_path)?.join("handshake")?).try_into()?;
-        let subscribe_endpoint = String::from(base_url.join(subscribe_base_path)?).try_into()?;
-        let connect_endpoint =
-            String::from(base_url.join(connect_base_path)?.join("connect")?).try_into()?;
-        let disconnect_endpoint =
-            String::from(base_url.join(disconnect_base_path)?.join("disconnect")?).try_into()?;
+        let endpoint_paths = EndpointPaths {
+            handshake: handshake_base_path.into(),
+            subscribe: subscribe_base_path.into(),
+            connect: connect_base_path.into(),
+            disconnect: disconnect_base_path.into(),
+            publish: publish_base_path.into(),
+        };
+        let endpoints = endpoints
+            .iter()
+            .cloned()
+            .map(|base_url| Endpoints::new(base_url, &endpoint_paths).map(Arc::new))
+            .collect::<CometdResult<Vec<_>>>()?;
+        let endpoint = endpoints
+            .first()
+            .cloned()
+            .map(ArcSwap::new)
+            .ok_or(CometdError::MissingEndpoint)?;
+        if connection_types.is_empty() {
+            return Err(CometdError::MissingConnectionType);
+        }
         let timeout = timeout.unwrap_or(DEFAULT_TIMEOUT_MS);
         let interval = interval.unwrap_or(DEFAULT_INTERVAL_MS);
+        let retry_policy =
+            retry_policy.unwrap_or_else(|| Box::new(AdviceRetry::new(number_of_retries, backoff)));
         let id = Default::default();
         let access_token = access_token
             .map(ArcSwapOption::from_pointee)
             .unwrap_or_default();
+        let SessionSnapshot {
+            client_id,
+            cookies: snapshot_cookies,
+            subscriptions,
+            advice,
+        } = snapshot.unwrap_or_default();
+        let cookies = if snapshot_cookies.is_empty() {
+            cookies
+        } else {
+            let mut cookies = cookies.unwrap_or_default();
+            for cookie in snapshot_cookies
+                .into_iter()
+                .filter_map(|cookie| Cookie::parse(cookie).ok())
+            {
+                cookies.add(cookie);
+            }
+            Some(cookies)
+        };
         let cookies_string_cache = cookies
             .as_ref()
             .map(CookieJarExt::make_string)
             .map(ArcSwapOption::from_pointee)
             .unwrap_or_default();
         let cookies = cookies.unwrap_or_default();
-        let client_id = Default::default();
+        let client_id = client_id
+            .map(String::into_boxed_str)
+            .map(ArcSwapOption::from_pointee)
+            .unwrap_or_default();
+        let advice = Mutex::new(advice);
+        // resumed subscriptions are kept until explicit unsubscribe
+        let subscription_refs = subscriptions
+            .iter()
+            .map(|channel| (channel.clone(), SubscriptionRefs::pinned()))
+            .collect();
+        let subscriptions = Mutex::new(subscriptions.into_iter().collect());
         let http_client = Client::builder().build_http();
 
         let (cmd_tx, cmd_rx) = mpsc::channel(commands_channel_capacity);
+        let (pushed_tx, pushed_rx) = mpsc::unbounded_channel();
+        let (notification_tx, notification_rx) = mpsc::unbounded_channel();
         let (event_tx, mut event_rx) = broadcast(events_channel_capacity);
         event_rx.set_await_active(false);
 
-        let inner = CometdClientInner {
-            handshake_endpoint,
-            subscribe_endpoint,
-            connect_endpoint,
-            disconnect_endpoint,
+        let inner = Arc::new(CometdClientInner {
+            endpoint_paths,
+            endpoints: Mutex::new(endpoints),
+            endpoint,
             timeout,
             interval,
-            number_of_retries,
+            retry_policy,
+            backoff,
+            supervised,
             id,
             access_token,
             cookies: Mutex::new(cookies),
             cookies_string_cache,
             client_id,
+            advice,
+            subscriptions,
             http_client,
             request_timeout,
-        };
+            connection_types: connection_types.into(),
+            available_connection_types: Mutex::new(connection_types.to_vec()),
+            extensions: extensions.into(),
+            ack,
+            timesync,
+            pushed_tx,
+            subscription_channel_capacity,
+            calls: Default::default(),
+            routes: Default::default(),
+            subscription_refs: Mutex::new(subscription_refs),
+            notification_tx,
+            #[cfg(feature = "websocket")]
+            websocket: Default::default(),
+        });
 
-        client_task::spawn(inner, cmd_rx, event_tx);
+        client_task::spawn(
+            Arc::clone(&inner),
+            cmd_rx,
+            pushed_rx,
+            notification_rx,
+            event_tx,
+        );
 
         Ok(CometdClient {
+            inner,
             cmd_tx,
             inactive_event_rx: event_rx.deactivate(),
         })
@@ -226,7 +358,29 @@ impl<'a, 'b, 'c, 'd, 'e> CometdClientBuilder<'a, 'b, 'c, 'd, 'e> {
         self
     }
 
+    /// Set cometd server publish url path.
+    ///
+    /// # Example
+    /// ```rust,no_run
+    /// # use cometd_client::{CometdClient, CometdClientBuilder};
+    /// # let _ = || -> cometd_client::types::CometdResult<_> {
+    /// # #[derive(serde::Deserialize)]
+    /// # struct Data { msg: String, }
+    ///     let client = CometdClientBuilder::new(&"http://[::1]:1025/notifications/".parse()?)
+    ///         .publish_base_path("pub/") // http://[::1]:1025/notifications/pub/
+    ///         .build()?;
+    /// # let app: CometdClient<Data> = client;
+    /// # Ok(()) };
+    /// ```
+    #[inline(always)]
+    #[must_use]
+    pub const fn publish_base_path(mut self, url: &'f str) -> Self {
+        self.publish_base_path = url;
+        self
+    }
+
     /// Set `timeout` option in handshake request.
+    /// It's used until server sends its own `timeout` advice.
     #[inline(always)]
     #[must_use]
     pub const fn timeout(mut self, timeout: Duration) -> Self {
@@ -235,6 +389,7 @@ impl<'a, 'b, 'c, 'd, 'e> CometdClientBuilder<'a, 'b, 'c, 'd, 'e> {
     }
 
     /// Set `interval` option in handshake request.
+    /// It's used until server sends its own `interval` advice.
     #[inline(always)]
     #[must_use]
     pub const fn interval(mut self, interval: Duration) -> Self {
@@ -291,6 +446,20 @@ impl<'a, 'b, 'c, 'd, 'e> CometdClientBuilder<'a, 'b, 'c, 'd, 'e> {
         self
     }
 
+    /// Set capacity of every subscription handle (look at [`CometdClient::subscribe`]).
+    ///
+    /// If handle isn't read fast enough, then its new messages are dropped and
+    /// [`CometdError::SubscriptionOverflow`] is sent to event channel.
+    #[inline(always)]
+    #[must_use]
+    pub const fn subscription_channel_capacity(
+        mut self,
+        subscription_channel_capacity: usize,
+    ) -> Self {
+        self.subscription_channel_capacity = subscription_channel_capacity;
+        self
+    }
+
     /// Set capacity of internal commands channel.
     #[inline(always)]
     #[must_use]
@@ -300,6 +469,9 @@ impl<'a, 'b, 'c, 'd, 'e> CometdClientBuilder<'a, 'b, 'c, 'd, 'e> {
     }
 
     /// Set number of retries for requests.
+    ///
+    /// It is used only by default retry policy ([`AdviceRetry`]),
+    /// so it's ignored if [`CometdClientBuilder::retry_policy`] was set.
     #[inline(always)]
     #[must_use]
     pub const fn number_of_retries(mut self, number_of_retries: usize) -> Self {
@@ -307,11 +479,200 @@ impl<'a, 'b, 'c, 'd, 'e> CometdClientBuilder<'a, 'b, 'c, 'd, 'e> {
         self
     }
 
+    /// Set backoff strategy for delays between retries.
+    ///
+    /// It is used by default retry policy ([`AdviceRetry`]) and by supervisor mode
+    /// (see [`CometdClientBuilder::supervised`]).
+    ///
+    /// # Example
+    /// ```rust,no_run
+    /// # use cometd_client::{CometdClient, CometdClientBuilder, types::{Backoff, Jitter}};
+    /// # use core::{slice, time::Duration};
+    /// # let _ = || -> cometd_client::types::CometdResult<_> {
+    ///     let backoff = Backoff::new(Duration::from_millis(50), Duration::from_secs(10))
+    ///         .multiplier(1.5)
+    ///         .jitter(Jitter::Decorrelated);
+    ///
+    ///     let client = CometdClientBuilder::new(&"http://[::1]:1025/notifications/".parse()?)
+    ///         .backoff(backoff)
+    ///         .build()?;
+    /// # let app: CometdClient<()> = client;
+    /// # Ok(()) };
+    /// ```
+    #[inline(always)]
+    #[must_use]
+    pub const fn backoff(mut self, backoff: Backoff) -> Self {
+        self.backoff = backoff;
+        self
+    }
+
+    /// Set retry policy which decides whether to redo failed request.
+    /// By default [`AdviceRetry`] with [`CometdClientBuilder::number_of_retries`]
+    /// and [`CometdClientBuilder::backoff`] is used.
+    ///
+    /// # Example
+    /// ```rust,no_run
+    /// # use cometd_client::{CometdClient, CometdClientBuilder, types::{retry_policy::TransportRetry, Backoff}};
+    /// # let _ = || -> cometd_client::types::CometdResult<_> {
+    ///     let client = CometdClientBuilder::new(&"http://[::1]:1025/notifications/".parse()?)
+    ///         .retry_policy(TransportRetry::new(10, Backoff::default()))
+    ///         .build()?;
+    /// # let app: CometdClient<()> = client;
+    /// # Ok(()) };
+    /// ```
+    #[inline(always)]
+    #[must_use]
+    pub fn retry_policy(self, retry_policy: impl RetryPolicy) -> Self {
+        Self {
+            retry_policy: Some(Box::new(retry_policy)),
+            ..self
+        }
+    }
+
+    /// Enable supervisor mode.
+    ///
+    /// In supervisor mode errors don't stop client, instead it sends error to event channel and
+    /// redo connect after [`CometdClientBuilder::backoff`] delay until client is dropped
+    /// or server advises `reconnect: none`.
+    /// Session is kept through transport errors (see [`crate::types::CometdError::is_transport`]),
+    /// after other errors (e.g. unparsable response or failed handshake) client does new handshake
+    /// and resubscribes.
+    #[inline(always)]
+    #[must_use]
+    pub const fn supervised(mut self, supervised: bool) -> Self {
+        self.supervised = supervised;
+        self
+    }
+
     /// Set requests timeout.
+    /// Connect requests additionally wait `timeout` advised by server.
     #[inline(always)]
     #[must_use]
     pub const fn request_timeout(mut self, request_timeout: Duration) -> Self {
         self.request_timeout = request_timeout;
         self
     }
+
+    /// Set transports which are used after handshake in order of preference,
+    /// default is [`ConnectionType::LongPolling`] only.
+    ///
+    /// Client uses first one which server supports.
+    /// If it can't be established (e.g. websocket upgrade is blocked by proxy),
+    /// then client falls back to next one.
+    /// Handshake fails if server doesn't support any of them.
+    ///
+    /// # Example
+    /// ```rust,no_run
+    /// # use cometd_client::{CometdClient, CometdClientBuilder, types::ConnectionType};
+    /// # let _ = || -> cometd_client::types::CometdResult<_> {
+    /// let client = CometdClientBuilder::new(&"http://[::1]:1025/cometd/".parse()?)
+    ///     .connection_types(&[ConnectionType::WebSocket, ConnectionType::LongPolling])
+    ///     .build()?;
+    /// # let client: CometdClient<()> = client;
+    /// # Ok(()) };
+    /// ```
+    #[inline(always)]
+    #[must_use]
+    pub const fn connection_types(mut self, connection_types: &'g [ConnectionType]) -> Self {
+        self.connection_types = connection_types;
+        self
+    }
+
+    /// Register Bayeux extension, extensions are called in order of registration.
+    ///
+    /// # Example
+    /// ```rust,no_run
+    /// # use cometd_client::{CometdClient, CometdClientBuilder, types::Extension};
+    /// # let _ = || -> cometd_client::types::CometdResult<_> {
+    /// #[derive(Debug)]
+    /// struct Logger;
+    ///
+    /// impl Extension for Logger {}
+    ///
+    /// let client = CometdClientBuilder::new(&"http://[::1]:1025/notifications/".parse()?)
+    ///     .extension(Logger)
+    ///     .build()?;
+    /// # let client: CometdClient<()> = client;
+    /// # Ok(()) };
+    /// ```
+    #[inline(always)]
+    #[must_use]
+    pub fn extension(mut self, extension: impl Extension) -> Self {
+        self.extensions.push(Arc::new(extension));
+        self
+    }
+
+    /// Enable acknowledgement extension (look at [`AckExtension`]),
+    /// id of last received batch can be read through [`CometdClient::ack`].
+    ///
+    /// # Example
+    /// ```rust,no_run
+    /// # use cometd_client::{CometdClient, CometdClientBuilder};
+    /// # let _ = || -> cometd_client::types::CometdResult<_> {
+    /// let client = CometdClientBuilder::new(&"http://[::1]:1025/notifications/".parse()?)
+    ///     .ack_extension()
+    ///     .build()?;
+    /// # let client: CometdClient<()> = client;
+    /// # Ok(()) };
+    /// ```
+    #[inline(always)]
+    #[must_use]
+    pub fn ack_extension(mut self) -> Self {
+        let ack = Arc::new(AckExtension::new());
+
+        self.extensions.push(Arc::clone(&ack) as Arc<dyn Extension>);
+        self.ack = Some(ack);
+        self
+    }
+
+    /// Enable timesync extension, its estimations can be read through [`CometdClient::timesync`].
+    ///
+    /// # Example
+    /// ```rust,no_run
+    /// # use cometd_client::{CometdClient, CometdClientBuilder};
+    /// # let _ = || -> cometd_client::types::CometdResult<_> {
+    /// let client = CometdClientBuilder::new(&"http://[::1]:1025/notifications/".parse()?)
+    ///     .timesync_extension()
+    ///     .build()?;
+    /// # let client: CometdClient<()> = client;
+    /// # Ok(()) };
+    /// ```
+    #[inline(always)]
+    #[must_use]
+    pub fn timesync_extension(mut self) -> Self {
+        let timesync = Arc::new(TimesyncExtension::new());
+
+        self.extensions
+            .push(Arc::clone(&timesync) as Arc<dyn Extension>);
+        self.timesync = Some(timesync);
+        self
+    }
+
+    /// Resume session from `snapshot` (look at [`CometdClient::into_snapshot`]).
+    ///
+    /// Client skips handshake and sends connect request with previous client id,
+    /// if server rejects it, then client does handshake and resubscribes to all channels
+    /// from `snapshot`. Commands aren't blocked by this check.
+    ///
+    /// Resumed channels don't have subscription handles: their messages are sent to event channel
+    /// and they are kept until [`CometdClient::unsubscribe`].
+    ///
+    /// # Example
+    /// ```rust,no_run
+    /// # use cometd_client::{CometdClient, CometdClientBuilder, types::SessionSnapshot};
+    /// # let _ = || -> Result<_, Box<dyn std::error::Error>> {
+    /// let snapshot = serde_json::from_slice::<SessionSnapshot>(&std::fs::read("session.json")?)?;
+    ///
+    /// let client = CometdClientBuilder::new(&"http://[::1]:1025/notifications/".parse()?)
+    ///     .resume(snapshot)
+    ///     .build()?;
+    /// # let client: CometdClient<()> = client;
+    /// # Ok(()) };
+    /// ```
+    #[inline(always)]
+    #[must_use]
+    pub fn resume(mut self, snapshot: SessionSnapshot) -> Self {
+        self.snapshot = Some(snapshot);
+        self
+    }
 }
@@ -1,74 +1,195 @@
 use crate::{client::CometdClientInner, retry_with_advice, types::*};
-use core::future::{ready, Future};
-use serde::de::DeserializeOwned;
+use core::slice;
+use core::{
+    future::{ready, Future},
+    time::Duration,
+};
 use std::sync::Arc;
-use tokio::select;
+use tokio::{select, sync::mpsc, time::sleep};
 
 #[inline(always)]
 pub(crate) fn spawn(
-    inner: CometdClientInner,
+    inner: Arc<CometdClientInner>,
     cmd_rx: CmdReceiver,
-    event_tx: EventSender<impl DeserializeOwned + Send + Sync + 'static>,
+    pushed_rx: mpsc::UnboundedReceiver<Vec<Message>>,
+    notification_rx: NotificationReceiver,
+    event_tx: EventSender<impl CometdMessage + Send + Sync + 'static>,
 ) {
     tokio::task::spawn(async move {
         let broadcast_event = |event| async {
             let _ = event_tx.broadcast(event).await;
         };
 
-        if let Err(error) = retry_with_advice(
-            inner.number_of_retries,
-            || ready(Ok(())),
-            || inner.handshake(),
-        )
-        .await
-        {
-            broadcast_event(CometdClientEvent::error(error)).await;
-        } else {
-            cmd_connect_loop(inner, cmd_rx, broadcast_event).await;
-        }
+        cmd_connect_loop(inner, cmd_rx, pushed_rx, notification_rx, broadcast_event).await;
     });
 }
 
 #[inline(always)]
 async fn cmd_connect_loop<Msg, Fut>(
-    inner: CometdClientInner,
+    inner: Arc<CometdClientInner>,
     mut cmd_rx: CmdReceiver,
+    mut pushed_rx: mpsc::UnboundedReceiver<Vec<Message>>,
+    mut notification_rx: NotificationReceiver,
     broadcast_event: impl Fn(CometdClientEvent<Msg>) -> Fut,
 ) where
-    Msg: DeserializeOwned,
+    Msg: CometdMessage,
     Fut: Future<Output = ()>,
 {
-    enum Res<Msg> {
+    enum Res {
         Left(Option<Command>),
-        Right(CometdResult<Arc<[Data<Msg>]>>),
+        Pushed(Vec<Message>),
+        Notified(Notification),
+        Right(CometdResult<()>),
     }
     use Res::*;
 
+    let mut error_delays = inner.backoff.iter();
+    // resumed session is checked by first connect in command loop,
+    // so commands aren't blocked by it
+    let mut resuming = inner.client_id.load().is_some();
+    // session can't be continued after error in supervisor mode, so next connect does handshake
+    let mut rehandshake = false;
+    let result = if resuming {
+        Ok(())
+    } else {
+        retry_with_advice(
+            &inner,
+            ErrorKind::Handshake,
+            || ready(Ok(())),
+            || inner.handshake(),
+        )
+        .await
+    };
+    let mut connect_delay = match result {
+        Ok(()) if resuming => Duration::ZERO,
+        Ok(()) => inner.connect_interval(),
+        // in supervisor mode client is stopped only by drop or server
+        Err(error) if inner.supervised && !inner.reconnect_forbidden() => {
+            inner.failover_on_error(&error);
+            broadcast_event(CometdClientEvent::error(error)).await;
+            rehandshake = true;
+            error_delays.next().unwrap_or_default()
+        }
+        Err(error) => {
+            broadcast_event(CometdClientEvent::error(error)).await;
+            return;
+        }
+    };
+
+    // connect request is kept between iterations, so commands and notifications
+    // don't drop messages which server already put into its reply
+    let mut connection = None;
     loop {
+        let connection_fut = connection.get_or_insert_with(|| {
+            Box::pin(next_connect(
+                &inner,
+                &broadcast_event,
+                connect_delay,
+                rehandshake,
+                resuming,
+            ))
+        });
         let res = select! {
             biased;
             cmd = cmd_rx.recv() => Left(cmd),
-            data = retry_with_advice(
-                inner.number_of_retries,
-                || inner.handshake(),
-                || inner.connect::<Msg>(),
-            ) => Right(data),
+            Some(messages) = pushed_rx.recv() => Pushed(messages),
+            Some(notification) = notification_rx.recv() => Notified(notification),
+            result = connection_fut => Right(result),
         };
+        if let Right(_) = res {
+            connection = None;
+        }
 
         match res {
-            Left(Some(Command::Subscribe(subscriptions))) => {
-                if let Err(error) = retry_with_advice(
-                    inner.number_of_retries,
-                    || inner.handshake(),
+            Left(Some(Command::Subscribe {
+                subscriptions,
+                reply_tx,
+            })) => {
+                let result = retry_with_advice(
+                    &inner,
+                    ErrorKind::Subscribe,
+                    || handshake(&inner, &broadcast_event),
                     || inner.subscribe(&subscriptions),
                 )
-                .await
-                {
-                    broadcast_event(CometdClientEvent::error(error)).await;
+                .await;
+                if let Ok(ref results) = result {
+                    for result in results.iter().filter(|result| result.1.is_ok()) {
+                        inner.pin_subscription(&result.0);
+                    }
+                }
+                let _ = reply_tx.send(result);
+            }
+            Left(Some(Command::Unsubscribe {
+                subscriptions,
+                reply_tx,
+            })) => {
+                let result = unsubscribe(&inner, &broadcast_event, &subscriptions).await;
+                let _ = reply_tx.send(result);
+            }
+            Left(Some(Command::Acquire { channel, reply_tx })) => {
+                let result = acquire(&inner, &broadcast_event, &channel).await;
+                let _ = reply_tx.send(result);
+            }
+            Left(Some(Command::Publish {
+                channel,
+                data,
+                reply_tx,
+            })) => {
+                let result = retry_with_advice(
+                    &inner,
+                    ErrorKind::Publish,
+                    || handshake(&inner, &broadcast_event),
+                    || inner.publish(&channel, &data),
+                )
+                .await;
+                let _ = reply_tx.send(result);
+            }
+            Left(Some(Command::Call {
+                id,
+                channel,
+                data,
+                reply_tx,
+            })) => {
+                let result = retry_with_advice(
+                    &inner,
+                    ErrorKind::Call,
+                    || handshake(&inner, &broadcast_event),
+                    || inner.call(&id, &channel, &data),
+                )
+                .await;
+                let _ = reply_tx.send(result);
+            }
+            Pushed(messages) => {
+                let messages = inner.apply_incoming_extensions(messages);
+                let messages = inner.route_call_replies(messages);
+                let messages = inner.route_messages(messages);
+                broadcast_messages(messages, &broadcast_event).await;
+            }
+            Notified(Notification::Released(channel)) => {
+                release(&inner, &broadcast_event, channel).await;
+            }
+            Notified(Notification::Error(error)) => {
+                broadcast_event(CometdClientEvent::error(error)).await;
+            }
+            Right(Ok(())) => {
+                resuming = false;
+                rehandshake = false;
+                // server asked to stop
+                if inner.reconnect_forbidden() {
                     break;
                 }
+
+                error_delays = inner.backoff.iter();
+                connect_delay = inner.connect_interval();
+            }
+            // in supervisor mode errors don't stop client unless server asked to stop,
+            // session is kept only through transport errors
+            Right(Err(error)) if inner.supervised && !inner.reconnect_forbidden() => {
+                inner.failover_on_error(&error);
+                rehandshake |= !error.is_transport();
+                connect_delay = error_delays.next().unwrap_or_default();
+                broadcast_event(CometdClientEvent::error(error)).await;
             }
-            Right(Ok(data)) => broadcast_event(CometdClientEvent::Message(data)).await,
             // communication errors
             Left(None) => break,
             Right(Err(error)) => {
@@ -78,7 +199,254 @@ async fn cmd_connect_loop<Msg, Fut>(
         }
     }
 
+    while let Ok(messages) = pushed_rx.try_recv() {
+        let messages = inner.apply_incoming_extensions(messages);
+        let messages = inner.route_call_replies(messages);
+        let messages = inner.route_messages(messages);
+        broadcast_messages(messages, &broadcast_event).await;
+    }
+    // subscription handles keep client state, but must be closed with client
+    inner.close_routes();
+
+    if inner.reconnect_forbidden() || inner.client_id.load().is_none() {
+        return;
+    }
+
     if let Err(error) = inner.disconnect().await {
         broadcast_event(CometdClientEvent::error(error)).await;
     }
 }
+
+/// Wait `delay` and send next connect request,
+/// handshake is done instead if session was lost or it's checked if it was resumed.
+#[inline]
+async fn next_connect<Msg, Fut>(
+    inner: &CometdClientInner,
+    broadcast_event: &impl Fn(CometdClientEvent<Msg>) -> Fut,
+    delay: Duration,
+    rehandshake: bool,
+    resuming: bool,
+) -> CometdResult<()>
+where
+    Fut: Future<Output = ()>,
+{
+    sleep(delay).await;
+
+    if rehandshake || inner.client_id.load().is_none() {
+        retry_with_advice(
+            inner,
+            ErrorKind::Handshake,
+            || ready(Ok(())),
+            || handshake(inner, broadcast_event),
+        )
+        .await
+    } else if resuming {
+        resume(inner, broadcast_event).await
+    } else {
+        retry_with_advice(
+            inner,
+            ErrorKind::Connect,
+            || handshake(inner, broadcast_event),
+            || inner.connect(),
+        )
+        .await
+    }
+}
+
+/// Do handshake and resubscribe to all active subscriptions,
+/// because server forgets them after new handshake.
+#[inline]
+async fn handshake<Msg, Fut>(
+    inner: &CometdClientInner,
+    broadcast_event: &impl Fn(CometdClientEvent<Msg>) -> Fut,
+) -> CometdResult<()>
+where
+    Fut: Future<Output = ()>,
+{
+    inner.handshake().await?;
+
+    for (subscription, result) in inner.resubscribe().await? {
+        if let Err(error) = result {
+            let error = CometdError::Resubscribe(subscription, Box::new(error));
+            broadcast_event(CometdClientEvent::error(error)).await;
+        }
+    }
+
+    Ok(())
+}
+
+/// Take subscription handle and subscribe to `channel` if client isn't subscribed yet.
+/// Handle is taken even if subscription fails, it's released by caller guard.
+#[inline]
+async fn acquire<Msg, Fut>(
+    inner: &CometdClientInner,
+    broadcast_event: &impl Fn(CometdClientEvent<Msg>) -> Fut,
+    channel: &ChannelId,
+) -> CometdResult<()>
+where
+    Fut: Future<Output = ()>,
+{
+    inner.acquire_subscription(channel);
+    if inner.subscriptions().contains(channel) {
+        return Ok(());
+    }
+
+    retry_with_advice(
+        inner,
+        ErrorKind::Subscribe,
+        || handshake(inner, broadcast_event),
+        || inner.subscribe(slice::from_ref(channel)),
+    )
+    .await
+    .and_then(|results| results.into_iter().try_for_each(|result| result.1))
+}
+
+/// Unpin `subscriptions` and unsubscribe from channels which have no subscription handles,
+/// other channels are reported as unsubscribed, but they are kept until handles are dropped.
+#[inline]
+async fn unsubscribe<Msg, Fut>(
+    inner: &CometdClientInner,
+    broadcast_event: &impl Fn(CometdClientEvent<Msg>) -> Fut,
+    subscriptions: &[ChannelId],
+) -> CometdResult<Vec<(ChannelId, CometdResult<()>)>>
+where
+    Fut: Future<Output = ()>,
+{
+    let released = subscriptions
+        .iter()
+        .filter(|channel| inner.unpin_subscription(channel))
+        .cloned()
+        .collect::<Vec<_>>();
+
+    let mut results = if released.is_empty() {
+        Vec::new()
+    } else {
+        let result = retry_with_advice(
+            inner,
+            ErrorKind::Unsubscribe,
+            || handshake(inner, broadcast_event),
+            || inner.unsubscribe(&released),
+        )
+        .await;
+
+        match result {
+            Ok(results) => results,
+            Err(error) => {
+                released
+                    .iter()
+                    .for_each(|channel| inner.pin_subscription(channel));
+                return Err(error);
+            }
+        }
+    };
+    for result in results.iter().filter(|result| result.1.is_err()) {
+        inner.pin_subscription(&result.0);
+    }
+
+    Ok(subscriptions
+        .iter()
+        .map(|channel| {
+            results
+                .iter()
+                .position(|result| result.0 == *channel)
+                .map_or_else(
+                    || (channel.clone(), Ok(())),
+                    |position| results.swap_remove(position),
+                )
+        })
+        .collect())
+}
+
+/// Release subscription handle and unsubscribe from `channel` if it was the last one.
+#[inline]
+async fn release<Msg, Fut>(
+    inner: &CometdClientInner,
+    broadcast_event: &impl Fn(CometdClientEvent<Msg>) -> Fut,
+    channel: ChannelId,
+) where
+    Fut: Future<Output = ()>,
+{
+    if !inner.release_subscription(&channel) || !inner.subscriptions().contains(&channel) {
+        return;
+    }
+
+    let result = retry_with_advice(
+        inner,
+        ErrorKind::Unsubscribe,
+        || handshake(inner, broadcast_event),
+        || inner.unsubscribe(slice::from_ref(&channel)),
+    )
+    .await;
+
+    match result {
+        Ok(results) => {
+            for error in results.into_iter().filter_map(|result| result.1.err()) {
+                broadcast_event(CometdClientEvent::error(error)).await;
+            }
+        }
+        Err(error) => broadcast_event(CometdClientEvent::error(error)).await,
+    }
+}
+
+/// Check resumed session by connect request with previous client id,
+/// do handshake and resubscribe only if server rejects it.
+#[inline]
+async fn resume<Msg, Fut>(
+    inner: &CometdClientInner,
+    broadcast_event: &impl Fn(CometdClientEvent<Msg>) -> Fut,
+) -> CometdResult<()>
+where
+    Fut: Future<Output = ()>,
+{
+    match inner.connect().await {
+        Err(CometdError::WrongResponse(..)) => {
+            retry_with_advice(
+                inner,
+                ErrorKind::Handshake,
+                || ready(Ok(())),
+                || handshake(inner, broadcast_event),
+            )
+            .await
+        }
+        result => result,
+    }
+}
+
+/// Broadcast messages which were pushed by server, meta messages are skipped.
+/// Messages which can't be delivered are broadcasted as separate errors, so they don't drop others.
+#[inline]
+async fn broadcast_messages<Msg, Fut>(
+    messages: Vec<Message>,
+    broadcast_event: &impl Fn(CometdClientEvent<Msg>) -> Fut,
+) where
+    Msg: CometdMessage,
+    Fut: Future<Output = ()>,
+{
+    let (data, errors) = messages
+        .into_iter()
+        .filter(|message| !message.is_meta())
+        .map(|message| {
+            let Message { channel, data, .. } = message;
+            let channel = channel.map(ChannelId::new).transpose()?;
+            let message = parse_message(channel.as_ref(), data)?;
+
+            Ok(Data { channel, message })
+        })
+        .fold(
+            (Vec::new(), Vec::new()),
+            |(mut data, mut errors), result: CometdResult<_>| {
+                match result {
+                    Ok(message) => data.push(message),
+                    Err(error) => errors.push(error),
+                }
+                (data, errors)
+            },
+        );
+
+    if !data.is_empty() {
+        broadcast_event(CometdClientEvent::Message(data.into())).await;
+    }
+    for error in errors {
+        broadcast_event(CometdClientEvent::error(error)).await;
+    }
+}
@@ -0,0 +1,124 @@
+use crate::{
+    types::{Advice, ChannelId, CometdError, CometdResult, ErrorKind, Message, Reconnect},
+    CometdClientInner,
+};
+use serde_json::Value as JsonValue;
+use std::sync::PoisonError;
+use tokio::sync::oneshot;
+
+const KIND: ErrorKind = ErrorKind::Call;
+
+impl CometdClientInner {
+    /// Send `data` to service `channel` with request `id`.
+    ///
+    /// Return result if server replied with it, otherwise it comes later
+    /// with pushed messages and is routed to caller by `route_call_replies`.
+    pub(crate) async fn call(
+        &self,
+        id: &str,
+        channel: &ChannelId,
+        data: &JsonValue,
+    ) -> CometdResult<Option<JsonValue>> {
+        let client_id = self
+            .client_id
+            .load_full()
+            .ok_or(CometdError::MissingClientId(KIND))?;
+        let message = Message {
+            id: Some(id.to_owned()),
+            channel: Some(channel.to_string()),
+            data: Some(data.clone()),
+            client_id: Some((*client_id).clone()),
+            ..Default::default()
+        };
+
+        // server can send acknowledgement and result separately
+        let reply = self
+            .send_messages(vec![message], KIND)
+            .await?
+            .into_iter()
+            .filter(|reply| reply.id.as_deref() == Some(id))
+            .max_by_key(|reply| reply.data.is_some())
+            .ok_or_else(|| {
+                CometdError::wrong_response(
+                    KIND,
+                    Reconnect::None,
+                    "The response corresponding request id cannot be found.",
+                )
+            })?;
+
+        let has_result = reply.data.is_some();
+        call_result(reply).map(|data| has_result.then_some(data))
+    }
+
+    /// Register call with `id`, reply to it will be sent to returned receiver.
+    #[inline]
+    pub(crate) fn register_call(&self, id: String) -> oneshot::Receiver<Message> {
+        let (reply_tx, reply_rx) = oneshot::channel();
+        self.calls
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .insert(id, reply_tx);
+
+        reply_rx
+    }
+
+    #[inline]
+    pub(crate) fn forget_call(&self, id: &str) {
+        self.calls
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .remove(id);
+    }
+
+    /// Pass replies to pending calls to callers, return other messages.
+    pub(crate) fn route_call_replies(&self, messages: Vec<Message>) -> Vec<Message> {
+        let mut calls = self.calls.lock().unwrap_or_else(PoisonError::into_inner);
+        if calls.is_empty() {
+            return messages;
+        }
+
+        messages
+            .into_iter()
+            .filter_map(|message| {
+                match message
+                    .id
+                    .as_ref()
+                    .filter(|_| !message.is_meta())
+                    .and_then(|id| calls.remove(id))
+                {
+                    Some(reply_tx) => {
+                        let _ = reply_tx.send(message);
+                        None
+                    }
+                    None => Some(message),
+                }
+            })
+            .collect()
+    }
+}
+
+/// Return call result from server `reply`.
+pub(crate) fn call_result(reply: Message) -> CometdResult<JsonValue> {
+    let Message {
+        successful,
+        error,
+        advice,
+        data,
+        ..
+    } = reply;
+
+    if successful == Some(false) {
+        // failed service replies can describe error in data
+        let error = error
+            .or_else(|| data.as_ref().map(JsonValue::to_string))
+            .unwrap_or_default();
+
+        Err(CometdError::wrong_response(
+            KIND,
+            Advice::reconnect(advice),
+            error,
+        ))
+    } else {
+        Ok(data.unwrap_or_default())
+    }
+}
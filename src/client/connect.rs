@@ -1,76 +1,42 @@
 use crate::{
-    types::{Advice, CometdError, CometdResult, Data, ErrorKind, Message, Reconnect},
+    types::{Advice, CometdError, CometdResult, ErrorKind, Message},
     CometdClientInner,
 };
-use serde::de::DeserializeOwned;
-use serde_json::json;
-use std::sync::Arc;
 
 impl CometdClientInner {
-    pub(crate) async fn connect<Msg>(&self) -> CometdResult<Arc<[Data<Msg>]>>
-    where
-        Msg: DeserializeOwned,
-    {
+    /// Send connect request, messages which server pushes with reply are passed to client task.
+    /// `connectionType` is set by transport which sends request.
+    pub(crate) async fn connect(&self) -> CometdResult<()> {
         const KIND: ErrorKind = ErrorKind::Connect;
 
         let client_id = self
             .client_id
             .load_full()
             .ok_or(CometdError::MissingClientId(KIND))?;
-        let id = self.next_id();
-        let body = json!([{
-          "id": id,
-          "channel": "/meta/connect",
-          "connectionType": "long-polling",
-          "clientId": *client_id
-        }])
-        .to_string();
-
-        let request_builder = self.create_request_builder(&self.connect_endpoint);
-
-        let mut messages = self
-            .send_request_and_parse_json_body::<Vec<Message>>(request_builder, body, KIND)
-            .await?;
-
-        if let Some(position) = messages
-            .iter()
-            .position(|message| message.id.as_ref() == Some(&id))
-        {
-            let Message {
-                successful,
-                error,
-                advice,
-                ..
-            } = messages.remove(position);
-
-            if successful == Some(false) {
-                Err(CometdError::wrong_response(
-                    KIND,
-                    Advice::reconnect(advice),
-                    error.unwrap_or_default(),
-                ))
-            } else {
-                let data = messages
-                    .into_iter()
-                    .map(|message| {
-                        let Message { channel, data, .. } = message;
-                        let message = data
-                            .map(serde_json::from_value::<Msg>)
-                            .transpose()
-                            .map_err(|error| CometdError::ParseBody(KIND, error))?;
-
-                        Ok::<_, CometdError>(Data { channel, message })
-                    })
-                    .collect::<CometdResult<_>>()?;
-
-                Ok(data)
-            }
-        } else {
+        let message = Message {
+            id: Some(self.next_id()),
+            channel: Some("/meta/connect".to_owned()),
+            client_id: Some((*client_id).clone()),
+            ..Default::default()
+        };
+
+        let Message {
+            successful,
+            error,
+            advice,
+            ..
+        } = self.send_message(message, KIND).await?;
+
+        self.update_advice(advice.as_ref());
+
+        if successful == Some(false) {
             Err(CometdError::wrong_response(
                 KIND,
-                Reconnect::None,
-                "The response corresponding request id cannot be found.",
+                Advice::reconnect(advice),
+                error.unwrap_or_default(),
             ))
+        } else {
+            Ok(())
         }
     }
 }
@@ -0,0 +1,70 @@
+use crate::{types::ConnectionType, CometdClientInner};
+use std::sync::{MutexGuard, PoisonError};
+
+impl CometdClientInner {
+    /// Return connection type which is currently used.
+    #[inline]
+    pub(crate) fn connection_type(&self) -> ConnectionType {
+        self.available_connection_types()
+            .first()
+            .copied()
+            .unwrap_or_default()
+    }
+
+    /// Return HTTP connection type which is used for handshake.
+    #[inline]
+    pub(crate) fn handshake_connection_type(&self) -> ConnectionType {
+        self.connection_types
+            .iter()
+            .copied()
+            .find(|connection_type| {
+                matches!(
+                    *connection_type,
+                    ConnectionType::LongPolling | ConnectionType::CallbackPolling
+                )
+            })
+            .unwrap_or_default()
+    }
+
+    /// Remember connection types which are supported both by client and by server
+    /// in order of client preference.
+    /// Return `false` if there is no such connection type.
+    pub(crate) fn negotiate_connection_types(&self, supported: &[String]) -> bool {
+        let negotiated = self
+            .connection_types
+            .iter()
+            .copied()
+            .filter(|connection_type| {
+                supported
+                    .iter()
+                    .any(|supported| supported == connection_type.as_str())
+            })
+            .collect::<Vec<_>>();
+        if negotiated.is_empty() {
+            return false;
+        }
+
+        *self.available_connection_types() = negotiated;
+        true
+    }
+
+    /// Stop using `failed` connection type and switch to next one.
+    /// Return `false` if there is no other connection type.
+    #[cfg_attr(not(feature = "websocket"), allow(dead_code))]
+    pub(crate) fn fallback_connection_type(&self, failed: ConnectionType) -> bool {
+        let mut available = self.available_connection_types();
+        if available.len() < 2 {
+            return false;
+        }
+
+        available.retain(|connection_type| *connection_type != failed);
+        true
+    }
+
+    #[inline(always)]
+    fn available_connection_types(&self) -> MutexGuard<'_, Vec<ConnectionType>> {
+        self.available_connection_types
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+    }
+}
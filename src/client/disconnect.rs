@@ -2,7 +2,6 @@ use crate::{
     types::{Advice, CometdError, CometdResult, ErrorKind, Message},
     CometdClientInner,
 };
-use serde_json::json;
 
 impl CometdClientInner {
     pub(crate) async fn disconnect(&self) -> CometdResult<()> {
@@ -12,24 +11,26 @@ impl CometdClientInner {
             .client_id
             .swap(None)
             .ok_or_else(|| CometdError::MissingClientId(KIND))?;
-        let body = json!([{
-          "id": self.next_id(),
-          "channel": "/meta/disconnect",
-          "clientId": *client_id
-        }])
-        .to_string();
+        let message = Message {
+            id: Some(self.next_id()),
+            channel: Some("/meta/disconnect".to_owned()),
+            client_id: Some((*client_id).clone()),
+            ..Default::default()
+        };
 
-        let request_builder = self.create_request_builder(&self.disconnect_endpoint);
+        let result = self.send_message(message, KIND).await;
+
+        #[cfg(feature = "websocket")]
+        self.close_websocket().await;
 
         let Message {
             successful,
             error,
             advice,
             ..
-        } = self
-            .send_request_and_parse_json_body::<[Message; 1]>(request_builder, body, KIND)
-            .await
-            .map(|[message]| message)?;
+        } = result?;
+
+        self.subscriptions().clear();
 
         if successful == Some(false) {
             Err(CometdError::wrong_response(
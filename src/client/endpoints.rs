@@ -0,0 +1,271 @@
+use crate::{
+    types::{CometdError, CometdResult, ErrorKind, Reconnect},
+    CometdClientInner,
+};
+use hyper::Uri;
+use std::sync::{Arc, PoisonError};
+use url::Url;
+
+/// Base paths of endpoints, they are same for every cometd server.
+#[derive(Debug)]
+pub(crate) struct EndpointPaths {
+    pub(crate) handshake: Box<str>,
+    pub(crate) subscribe: Box<str>,
+    pub(crate) connect: Box<str>,
+    pub(crate) disconnect: Box<str>,
+    pub(crate) publish: Box<str>,
+}
+
+/// Endpoints of one cometd server.
+#[derive(Debug)]
+pub(crate) struct Endpoints {
+    pub(crate) base_url: Url,
+    pub(crate) handshake: Uri,
+    pub(crate) subscribe: Uri,
+    pub(crate) connect: Uri,
+    pub(crate) disconnect: Uri,
+    pub(crate) publish: Uri,
+    #[cfg(feature = "websocket")]
+    pub(crate) websocket: Url,
+}
+
+impl Endpoints {
+    pub(crate) fn new(base_url: Url, paths: &EndpointPaths) -> CometdResult<Self> {
+        let handshake =
+            String::from(base_url.join(&paths.handshake)?.join("handshake")?).try_into()?;
+        let subscribe = String::from(base_url.join(&paths.subscribe)?).try_into()?;
+        let connect = String::from(base_url.join(&paths.connect)?.join("connect")?).try_into()?;
+        let disconnect =
+            String::from(base_url.join(&paths.disconnect)?.join("disconnect")?).try_into()?;
+        let publish = String::from(base_url.join(&paths.publish)?).try_into()?;
+        #[cfg(feature = "websocket")]
+        let websocket = {
+            let mut websocket = base_url.clone();
+            let scheme = if base_url.scheme() == "https" {
+                "wss"
+            } else {
+                "ws"
+            };
+            let _ = websocket.set_scheme(scheme);
+            websocket
+        };
+
+        Ok(Self {
+            base_url,
+            handshake,
+            subscribe,
+            connect,
+            disconnect,
+            publish,
+            #[cfg(feature = "websocket")]
+            websocket,
+        })
+    }
+
+    /// Return endpoint for requests of `kind`.
+    #[inline]
+    pub(crate) const fn uri(&self, kind: ErrorKind) -> &Uri {
+        match kind {
+            ErrorKind::Handshake => &self.handshake,
+            ErrorKind::Subscribe | ErrorKind::Unsubscribe => &self.subscribe,
+            ErrorKind::Connect => &self.connect,
+            ErrorKind::Disconnect => &self.disconnect,
+            ErrorKind::Publish | ErrorKind::Call => &self.publish,
+        }
+    }
+}
+
+impl CometdClientInner {
+    #[inline(always)]
+    pub(crate) fn endpoint(&self) -> Arc<Endpoints> {
+        self.endpoint.load_full()
+    }
+
+    /// Switch to next endpoint and forget client id, because it's valid only for previous server.
+    /// Do nothing if there is only one endpoint.
+    pub(crate) fn failover(&self) {
+        let endpoints = self
+            .endpoints
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner);
+        if endpoints.len() < 2 {
+            return;
+        }
+
+        let current = self.endpoint.load();
+        let position = endpoints
+            .iter()
+            .position(|endpoint| endpoint.base_url == current.base_url)
+            .unwrap_or_default();
+
+        if let Some(next) = endpoints.iter().cycle().nth(position + 1) {
+            self.endpoint.store(Arc::clone(next));
+            self.client_id.store(None);
+        }
+    }
+
+    /// Switch to next endpoint if current server is unreachable or it advised handshake
+    /// with other hosts. Handshake denial isn't reason to switch, because it's server decision.
+    pub(crate) fn failover_on_error(&self, error: &CometdError) {
+        let failover = match *error {
+            CometdError::WrongResponse(kind, Reconnect::Handshake, _) => {
+                !matches!(kind, ErrorKind::Handshake)
+                    && self.advice().hosts.is_some_and(|hosts| !hosts.is_empty())
+            }
+            ref error => error.is_transport(),
+        };
+
+        if failover {
+            self.failover();
+        }
+    }
+
+    /// Add servers from `advice.hosts` to endpoints list.
+    pub(crate) fn add_hosts(&self, hosts: &[String]) {
+        let current = self.endpoint.load();
+        let mut endpoints = self
+            .endpoints
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner);
+
+        for base_url in hosts
+            .iter()
+            .filter_map(|host| host_base_url(&current.base_url, host))
+        {
+            if endpoints
+                .iter()
+                .all(|endpoint| endpoint.base_url != base_url)
+            {
+                if let Ok(new_endpoints) = Endpoints::new(base_url, &self.endpoint_paths) {
+                    endpoints.push(Arc::new(new_endpoints));
+                }
+            }
+        }
+    }
+}
+
+/// Make base url for `host` (`host[:port]` or full url) with scheme and path of `base_url`.
+#[inline]
+fn host_base_url(base_url: &Url, host: &str) -> Option<Url> {
+    if host.contains("://") {
+        Url::parse(host).ok()
+    } else {
+        let mut url = Url::parse(&format!("{}://{host}", base_url.scheme())).ok()?;
+        url.set_path(base_url.path());
+        Some(url)
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used, clippy::indexing_slicing)]
+mod tests {
+    use super::*;
+    use crate::{types::Advice, CometdClientBuilder};
+
+    #[test]
+    fn test_host_base_url() {
+        let base_url = Url::parse("https://example.com:8443/cometd/").unwrap();
+
+        assert_eq!(
+            host_base_url(&base_url, "node1.example.com:9443").map(String::from),
+            Some("https://node1.example.com:9443/cometd/".to_owned())
+        );
+        assert_eq!(
+            host_base_url(&base_url, "http://node2.example.com/bayeux/").map(String::from),
+            Some("http://node2.example.com/bayeux/".to_owned())
+        );
+        assert_eq!(host_base_url(&base_url, "node3:port"), None);
+    }
+
+    #[test]
+    fn test_failover() {
+        let runtime = tokio::runtime::Builder::new_current_thread()
+            .build()
+            .unwrap();
+        // client task isn't polled, so endpoint is switched only by test
+        let _runtime_guard = runtime.enter();
+        let endpoints = [
+            Url::parse("http://127.0.0.1:1/cometd/").unwrap(),
+            Url::parse("http://127.0.0.1:2/cometd/").unwrap(),
+        ];
+        let client = CometdClientBuilder::with_endpoints(&endpoints)
+            .build::<()>()
+            .unwrap();
+        let inner = &client.inner;
+
+        inner.client_id.store(Some(Arc::new("client-1".into())));
+        inner.failover();
+        assert_eq!(client.endpoint(), endpoints[1]);
+        assert!(inner.client_id.load().is_none());
+
+        inner.failover();
+        assert_eq!(client.endpoint(), endpoints[0]);
+
+        inner.add_hosts(&["127.0.0.1:3".to_owned(), "127.0.0.1:2".to_owned()]);
+        inner.failover();
+        inner.failover();
+        assert_eq!(client.endpoint().as_str(), "http://127.0.0.1:3/cometd/");
+    }
+
+    #[test]
+    fn test_failover_with_single_endpoint() {
+        let runtime = tokio::runtime::Builder::new_current_thread()
+            .build()
+            .unwrap();
+        let _runtime_guard = runtime.enter();
+        let endpoint = Url::parse("http://127.0.0.1:1/cometd/").unwrap();
+        let client = CometdClientBuilder::new(&endpoint).build::<()>().unwrap();
+
+        client
+            .inner
+            .client_id
+            .store(Some(Arc::new("client-1".into())));
+        client.inner.failover();
+
+        assert_eq!(client.endpoint(), endpoint);
+        assert!(client.inner.client_id.load().is_some());
+    }
+
+    #[test]
+    fn test_failover_on_error() {
+        let runtime = tokio::runtime::Builder::new_current_thread()
+            .build()
+            .unwrap();
+        let _runtime_guard = runtime.enter();
+        let endpoints = [
+            Url::parse("http://127.0.0.1:1/cometd/").unwrap(),
+            Url::parse("http://127.0.0.1:2/cometd/").unwrap(),
+        ];
+        let client = CometdClientBuilder::with_endpoints(&endpoints)
+            .build::<()>()
+            .unwrap();
+        let inner = &client.inner;
+
+        // server decisions don't switch server
+        inner.failover_on_error(&CometdError::wrong_response(
+            ErrorKind::Handshake,
+            Reconnect::Handshake,
+            "403::Forbidden",
+        ));
+        inner.failover_on_error(&CometdError::wrong_response(
+            ErrorKind::Connect,
+            Reconnect::Handshake,
+            "402::Unknown client",
+        ));
+        assert_eq!(client.endpoint(), endpoints[0]);
+
+        inner.failover_on_error(&CometdError::RequestTimeout(ErrorKind::Publish));
+        assert_eq!(client.endpoint(), endpoints[1]);
+
+        inner.update_advice(Some(&Advice {
+            hosts: Some(vec!["127.0.0.1:3".to_owned()]),
+            ..Default::default()
+        }));
+        inner.failover_on_error(&CometdError::wrong_response(
+            ErrorKind::Connect,
+            Reconnect::Handshake,
+            "402::Unknown client",
+        ));
+        assert_eq!(client.endpoint().as_str(), "http://127.0.0.1:3/cometd/");
+    }
+}
@@ -0,0 +1,47 @@
+use crate::{types::Message, CometdClientInner};
+
+impl CometdClientInner {
+    /// Pass messages through extensions before sending, dropped messages are removed.
+    #[inline]
+    pub(crate) fn apply_outgoing_extensions(&self, messages: Vec<Message>) -> Vec<Message> {
+        messages
+            .into_iter()
+            .filter_map(|mut message| {
+                let is_meta = message.is_meta();
+
+                self.extensions
+                    .iter()
+                    .all(|extension| {
+                        if is_meta {
+                            extension.outgoing_meta(&mut message)
+                        } else {
+                            extension.outgoing(&mut message)
+                        }
+                    })
+                    .then_some(message)
+            })
+            .collect()
+    }
+
+    /// Pass received messages through extensions, dropped messages are removed.
+    #[inline]
+    pub(crate) fn apply_incoming_extensions(&self, messages: Vec<Message>) -> Vec<Message> {
+        messages
+            .into_iter()
+            .filter_map(|mut message| {
+                let is_meta = message.is_meta();
+
+                self.extensions
+                    .iter()
+                    .all(|extension| {
+                        if is_meta {
+                            extension.incoming_meta(&mut message)
+                        } else {
+                            extension.incoming(&mut message)
+                        }
+                    })
+                    .then_some(message)
+            })
+            .collect()
+    }
+}
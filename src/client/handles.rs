@@ -0,0 +1,85 @@
+use crate::{
+    types::{ChannelId, Notification},
+    CometdClientInner,
+};
+use std::sync::PoisonError;
+
+/// References which keep server subscription to channel.
+#[derive(Debug, Default)]
+pub(crate) struct SubscriptionRefs {
+    /// Number of alive subscription handles.
+    handles: usize,
+    /// Channel was subscribed through `CometdClient::subscribe_batch` or resumed session.
+    pinned: bool,
+}
+
+impl SubscriptionRefs {
+    #[inline(always)]
+    pub(crate) const fn pinned() -> Self {
+        Self {
+            handles: 0,
+            pinned: true,
+        }
+    }
+}
+
+impl CometdClientInner {
+    /// Increase number of subscription handles to `channel`.
+    ///
+    /// References are counted only by client task, so counting is ordered
+    /// with subscribe and unsubscribe requests.
+    #[inline]
+    pub(crate) fn acquire_subscription(&self, channel: &ChannelId) {
+        self.update_subscription_refs(channel, |refs| refs.handles += 1);
+    }
+
+    /// Decrease number of subscription handles to `channel`,
+    /// return `true` if nothing keeps subscription anymore.
+    #[inline]
+    pub(crate) fn release_subscription(&self, channel: &ChannelId) -> bool {
+        self.update_subscription_refs(channel, |refs| {
+            refs.handles = refs.handles.saturating_sub(1);
+        })
+    }
+
+    /// Keep subscription to `channel` until it's unpinned.
+    #[inline]
+    pub(crate) fn pin_subscription(&self, channel: &ChannelId) {
+        self.update_subscription_refs(channel, |refs| refs.pinned = true);
+    }
+
+    /// Remove pin from subscription to `channel`,
+    /// return `true` if nothing keeps subscription anymore.
+    #[inline]
+    pub(crate) fn unpin_subscription(&self, channel: &ChannelId) -> bool {
+        self.update_subscription_refs(channel, |refs| refs.pinned = false)
+    }
+
+    /// Send notification to client task.
+    #[inline(always)]
+    pub(crate) fn notify(&self, notification: Notification) {
+        let _ = self.notification_tx.send(notification);
+    }
+
+    #[inline]
+    fn update_subscription_refs(
+        &self,
+        channel: &ChannelId,
+        update: impl FnOnce(&mut SubscriptionRefs),
+    ) -> bool {
+        let mut refs_map = self
+            .subscription_refs
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner);
+
+        let refs = refs_map.entry(channel.clone()).or_default();
+        update(refs);
+
+        let unreferenced = refs.handles == 0 && !refs.pinned;
+        if unreferenced {
+            refs_map.remove(channel);
+        }
+
+        unreferenced
+    }
+}
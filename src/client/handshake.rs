@@ -2,26 +2,30 @@ use crate::{
     types::{Advice, CometdError, CometdResult, ErrorKind, Message, Reconnect},
     ArcSwapOptionExt as _, CometdClientInner,
 };
-use serde_json::json;
+use core::time::Duration;
 
 impl CometdClientInner {
     pub(crate) async fn handshake(&self) -> CometdResult<()> {
         const KIND: ErrorKind = ErrorKind::Handshake;
 
-        let body = json!([{
-          "id": self.next_id(),
-          "version": "1.0",
-          "minimumVersion": "1.0",
-          "channel": "/meta/handshake",
-          "supportedConnectionTypes": [ "long-polling" ],
-          "advice": {
-            "timeout": self.timeout.as_millis(),
-            "interval": self.interval.as_millis(),
-          }
-        }])
-        .to_string();
-
-        let request_builder = self.create_request_builder(&self.handshake_endpoint);
+        let connection_types = self
+            .connection_types
+            .iter()
+            .map(|connection_type| connection_type.as_str().to_owned())
+            .collect::<Vec<_>>();
+        let message = Message {
+            id: Some(self.next_id()),
+            version: Some("1.0".to_owned()),
+            minimum_version: Some("1.0".to_owned()),
+            channel: Some("/meta/handshake".to_owned()),
+            supported_connection_types: Some(connection_types.clone()),
+            advice: Some(Advice {
+                timeout: Some(as_millis(self.timeout)),
+                interval: Some(as_millis(self.interval)),
+                ..Default::default()
+            }),
+            ..Default::default()
+        };
 
         let Message {
             client_id,
@@ -30,10 +34,9 @@ impl CometdClientInner {
             error,
             advice,
             ..
-        } = self
-            .send_request_and_parse_json_body::<[Message; 1]>(request_builder, body, KIND)
-            .await
-            .map(|[message]| message)?;
+        } = self.send_message(message, KIND).await?;
+
+        self.update_advice(advice.as_ref());
 
         if successful == Some(false) {
             Err(CometdError::wrong_response(
@@ -41,13 +44,11 @@ impl CometdClientInner {
                 Advice::reconnect(advice),
                 error.unwrap_or_default(),
             ))
-        } else if !supported_connection_types
-            .iter()
-            .flatten()
-            .any(|connection_type| connection_type == "long-polling")
+        } else if !self
+            .negotiate_connection_types(supported_connection_types.as_deref().unwrap_or_default())
         {
             let msg = format!(
-                "Server doesn't support long-polling mode: `{supported_connection_types:?}`."
+                "Server doesn't support any of {connection_types:?} modes: `{supported_connection_types:?}`."
             );
 
             Err(CometdError::wrong_response(KIND, Reconnect::None, msg))
@@ -64,3 +65,8 @@ impl CometdClientInner {
         }
     }
 }
+
+#[inline(always)]
+fn as_millis(duration: Duration) -> u64 {
+    u64::try_from(duration.as_millis()).unwrap_or(u64::MAX)
+}
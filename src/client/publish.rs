@@ -0,0 +1,37 @@
+use crate::{
+    types::{Advice, ChannelId, CometdError, CometdResult, ErrorKind, Message},
+    CometdClientInner,
+};
+use serde_json::Value as JsonValue;
+
+impl CometdClientInner {
+    pub(crate) async fn publish(&self, channel: &ChannelId, data: &JsonValue) -> CometdResult<()> {
+        const KIND: ErrorKind = ErrorKind::Publish;
+
+        let client_id = self
+            .client_id
+            .load_full()
+            .ok_or_else(|| CometdError::MissingClientId(KIND))?;
+        let message = Message {
+            id: Some(self.next_id()),
+            channel: Some(channel.to_string()),
+            data: Some(data.clone()),
+            client_id: Some((*client_id).clone()),
+            ..Default::default()
+        };
+
+        match self.send_message(message, KIND).await? {
+            Message {
+                successful: Some(false),
+                error,
+                advice,
+                ..
+            } => Err(CometdError::wrong_response(
+                KIND,
+                Advice::reconnect(advice),
+                error.unwrap_or_default(),
+            )),
+            _ => Ok(()),
+        }
+    }
+}
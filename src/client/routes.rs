@@ -0,0 +1,98 @@
+use crate::{
+    types::{ChannelId, ChannelPattern, CometdError, Data, Message, Notification},
+    CometdClientInner,
+};
+use serde_json::Value as JsonValue;
+use std::sync::PoisonError;
+use tokio::sync::mpsc::{self, error::TrySendError};
+
+pub(crate) type RouteSender = mpsc::Sender<Data<JsonValue>>;
+pub(crate) type RouteReceiver = mpsc::Receiver<Data<JsonValue>>;
+
+/// Messages from channels matching `pattern` are sent to `tx` as raw json,
+/// messages taken by `exclusive` route aren't sent to event channel.
+///
+/// Messages are dropped while `tx` is full, it's reported once per overflow.
+#[derive(Debug)]
+pub(crate) struct Route {
+    pattern: ChannelPattern,
+    exclusive: bool,
+    tx: RouteSender,
+    overflowed: bool,
+}
+
+impl CometdClientInner {
+    /// Register route for channels matching `pattern`,
+    /// it's removed when returned receiver is dropped.
+    pub(crate) fn add_route(&self, pattern: ChannelPattern, exclusive: bool) -> RouteReceiver {
+        let (tx, rx) = mpsc::channel(self.subscription_channel_capacity.max(1));
+        self.routes
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .push(Route {
+                pattern,
+                exclusive,
+                tx,
+                overflowed: false,
+            });
+
+        rx
+    }
+
+    /// Remove all routes, so their receivers are closed.
+    #[inline]
+    pub(crate) fn close_routes(&self) {
+        self.routes
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .clear();
+    }
+
+    /// Pass messages to matching routes, return messages which weren't taken by exclusive route.
+    pub(crate) fn route_messages(&self, messages: Vec<Message>) -> Vec<Message> {
+        let mut routes = self.routes.lock().unwrap_or_else(PoisonError::into_inner);
+        routes.retain(|route| !route.tx.is_closed());
+        if routes.is_empty() {
+            return messages;
+        }
+
+        messages
+            .into_iter()
+            .filter(|message| {
+                let Some(channel) = message
+                    .channel
+                    .as_deref()
+                    .filter(|_| !message.is_meta())
+                    .and_then(|channel| ChannelId::new(channel).ok())
+                else {
+                    return true;
+                };
+
+                let mut taken = false;
+                for route in routes
+                    .iter_mut()
+                    .filter(|route| route.pattern.matches(&channel))
+                {
+                    let data = Data {
+                        channel: Some(channel.clone()),
+                        message: message.data.clone(),
+                    };
+                    match route.tx.try_send(data) {
+                        Ok(()) => route.overflowed = false,
+                        Err(TrySendError::Full(_)) if !route.overflowed => {
+                            route.overflowed = true;
+                            self.notify(Notification::Error(CometdError::SubscriptionOverflow(
+                                route.pattern.clone(),
+                            )));
+                        }
+                        Err(_) => {}
+                    }
+                    // message dropped by full route isn't sent to event channel either
+                    taken |= route.exclusive && !route.tx.is_closed();
+                }
+
+                !taken
+            })
+            .collect()
+    }
+}
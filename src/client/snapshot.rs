@@ -0,0 +1,25 @@
+use crate::{types::SessionSnapshot, CometdClientInner};
+use cookie::Cookie;
+use std::sync::PoisonError;
+
+impl CometdClientInner {
+    pub(crate) fn snapshot(&self) -> SessionSnapshot {
+        let client_id = self.client_id.load().as_deref().map(ToString::to_string);
+        let cookies = self
+            .cookies
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .iter()
+            .map(Cookie::to_string)
+            .collect();
+        let subscriptions = self.subscriptions().iter().cloned().collect();
+        let advice = self.advice();
+
+        SessionSnapshot {
+            client_id,
+            cookies,
+            subscriptions,
+            advice,
+        }
+    }
+}
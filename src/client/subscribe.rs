@@ -1,44 +1,42 @@
 use crate::{
-    types::{Advice, CometdError, CometdResult, ErrorKind, Message},
+    types::{ChannelId, CometdResult, ErrorKind},
     CometdClientInner,
 };
-use serde_json::{json, Value as JsonValue};
 
 impl CometdClientInner {
-    pub(crate) async fn subscribe(&self, subscriptions: &JsonValue) -> CometdResult<()> {
-        const KIND: ErrorKind = ErrorKind::Subscribe;
+    pub(crate) async fn subscribe(
+        &self,
+        subscriptions: &[ChannelId],
+    ) -> CometdResult<Vec<(ChannelId, CometdResult<()>)>> {
+        let results = self
+            .send_subscription_request("/meta/subscribe", subscriptions, ErrorKind::Subscribe)
+            .await?;
 
-        let client_id = self
-            .client_id
-            .load_full()
-            .ok_or_else(|| CometdError::MissingClientId(KIND))?;
-        let body = json!([{
-          "id": self.next_id(),
-          "channel": "/meta/subscribe",
-          "subscription": subscriptions,
-          "clientId": *client_id
-        }])
-        .to_string();
+        self.subscriptions().extend(
+            results
+                .iter()
+                .filter(|result| result.1.is_ok())
+                .map(|result| result.0.clone()),
+        );
 
-        let request_builder = self.create_request_builder(&self.subscribe_endpoint);
-        let Message {
-            successful,
-            error,
-            advice,
-            ..
-        } = self
-            .send_request_and_parse_json_body::<[Message; 1]>(request_builder, body, KIND)
-            .await
-            .map(|[message]| message)?;
+        Ok(results)
+    }
 
-        if successful == Some(false) {
-            Err(CometdError::wrong_response(
-                KIND,
-                Advice::reconnect(advice),
-                error.unwrap_or_default(),
-            ))
-        } else {
-            Ok(())
+    /// Redo subscribe for all active subscriptions.
+    /// Channels which server denied are removed from active subscriptions.
+    pub(crate) async fn resubscribe(&self) -> CometdResult<Vec<(ChannelId, CometdResult<()>)>> {
+        let subscriptions = self.subscriptions().iter().cloned().collect::<Box<[_]>>();
+        if subscriptions.is_empty() {
+            return Ok(Vec::new());
         }
+
+        let results = self.subscribe(&subscriptions).await?;
+
+        let mut active_subscriptions = self.subscriptions();
+        for result in results.iter().filter(|result| result.1.is_err()) {
+            active_subscriptions.remove(&result.0);
+        }
+
+        Ok(results)
     }
 }
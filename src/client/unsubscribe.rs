@@ -0,0 +1,22 @@
+use crate::{
+    types::{ChannelId, CometdResult, ErrorKind},
+    CometdClientInner,
+};
+
+impl CometdClientInner {
+    pub(crate) async fn unsubscribe(
+        &self,
+        subscriptions: &[ChannelId],
+    ) -> CometdResult<Vec<(ChannelId, CometdResult<()>)>> {
+        let results = self
+            .send_subscription_request("/meta/unsubscribe", subscriptions, ErrorKind::Unsubscribe)
+            .await?;
+
+        let mut active_subscriptions = self.subscriptions();
+        for result in results.iter().filter(|result| result.1.is_ok()) {
+            active_subscriptions.remove(&result.0);
+        }
+
+        Ok(results)
+    }
+}
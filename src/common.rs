@@ -1,2 +1,10 @@
+mod callback_polling;
 mod request_builder;
+mod send_messages;
 mod send_request;
+mod subscription_request;
+#[cfg(feature = "websocket")]
+mod websocket;
+
+#[cfg(feature = "websocket")]
+pub(crate) use websocket::*;
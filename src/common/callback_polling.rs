@@ -0,0 +1,88 @@
+use crate::{
+    consts::JSONP_CALLBACK,
+    types::{CometdError, CometdResult, ErrorKind, Message},
+    CometdClientInner,
+};
+use hyper::Uri;
+use url::Url;
+
+impl CometdClientInner {
+    /// Send `messages` in `message` query parameter of GET request
+    /// and unwrap JSONP response.
+    pub(crate) async fn send_callback_messages(
+        &self,
+        messages: &[Message],
+        kind: ErrorKind,
+    ) -> CometdResult<Vec<Message>> {
+        let message = serde_json::to_string(messages)
+            .map_err(|error| CometdError::SerializeData(kind, error))?;
+
+        let mut url = Url::parse(&self.endpoint().uri(kind).to_string())?;
+        url.query_pairs_mut()
+            .append_pair("jsonp", JSONP_CALLBACK)
+            .append_pair("message", &message);
+        let uri = Uri::try_from(String::from(url))?;
+
+        let request_builder = self.create_get_request_builder(&uri);
+        let raw_body = self
+            .send_request_and_fetch_body(request_builder, String::new(), kind)
+            .await?;
+
+        serde_json::from_slice(unwrap_jsonp(&raw_body))
+            .map_err(|error| CometdError::ParseBody(kind, error))
+    }
+}
+
+/// Extract `...` from `callback(...)`, return body as is if it isn't wrapped.
+#[inline]
+fn unwrap_jsonp(body: &[u8]) -> &[u8] {
+    let trimmed = trim_ascii(body);
+    let trimmed = trimmed.strip_suffix(b";").map_or(trimmed, trim_ascii);
+
+    trimmed
+        .strip_prefix(JSONP_CALLBACK.as_bytes())
+        .map(trim_ascii)
+        .and_then(|rest| rest.strip_prefix(b"("))
+        .and_then(|rest| rest.strip_suffix(b")"))
+        .unwrap_or(body)
+}
+
+/// Trim ASCII whitespaces, `<[u8]>::trim_ascii` isn't used to keep supported Rust version.
+#[inline]
+fn trim_ascii(bytes: &[u8]) -> &[u8] {
+    let start = bytes
+        .iter()
+        .position(|byte| !byte.is_ascii_whitespace())
+        .unwrap_or(bytes.len());
+    let end = bytes
+        .iter()
+        .rposition(|byte| !byte.is_ascii_whitespace())
+        .map_or(start, |end| end + 1);
+
+    bytes.get(start..end).unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_unwrap_jsonp() {
+        let wrapped = format!("{JSONP_CALLBACK}([{{\"channel\":\"/meta/connect\"}}]);");
+        assert_eq!(
+            unwrap_jsonp(wrapped.as_bytes()),
+            br#"[{"channel":"/meta/connect"}]"#
+        );
+
+        let spaced = format!(" \n{JSONP_CALLBACK} ( [] ) ;\n");
+        assert_eq!(unwrap_jsonp(spaced.as_bytes()), b" [] ");
+
+        let without_semicolon = format!("{JSONP_CALLBACK}([])");
+        assert_eq!(unwrap_jsonp(without_semicolon.as_bytes()), b"[]");
+
+        // not wrapped or wrapped by other callback
+        assert_eq!(unwrap_jsonp(b"[]"), b"[]");
+        assert_eq!(unwrap_jsonp(b"other([]);"), b"other([]);");
+        assert_eq!(unwrap_jsonp(b""), b"");
+    }
+}
@@ -8,11 +8,24 @@ use hyper::{
 impl CometdClientInner {
     #[inline]
     pub(crate) fn create_request_builder(&self, uri: &Uri) -> Builder {
-        let mut ret = Request::builder()
+        let ret = Request::builder()
             .uri(uri)
             .method(Method::POST)
             .header(CONTENT_TYPE, APPLICATION_JSON);
 
+        self.set_credentials(ret)
+    }
+
+    /// Create GET request builder which is used by `callback-polling`.
+    #[inline]
+    pub(crate) fn create_get_request_builder(&self, uri: &Uri) -> Builder {
+        let ret = Request::builder().uri(uri).method(Method::GET);
+
+        self.set_credentials(ret)
+    }
+
+    #[inline]
+    fn set_credentials(&self, mut ret: Builder) -> Builder {
         // set authorization header
         if let Some(token) = self
             .access_token
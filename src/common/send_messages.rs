@@ -0,0 +1,120 @@
+#[cfg(feature = "websocket")]
+use crate::common::is_upgrade_error;
+use crate::{
+    types::{CometdError, CometdResult, ConnectionType, ErrorKind, Message, Reconnect},
+    CometdClientInner,
+};
+
+impl CometdClientInner {
+    /// Send `messages` through current transport and return server replies to them.
+    /// Other messages from server response are passed to client task.
+    ///
+    /// Messages and replies are passed through extensions.
+    ///
+    /// Handshake is always sent with HTTP request.
+    /// If websocket can't be opened, then next negotiated connection type is used.
+    #[cfg_attr(not(feature = "websocket"), allow(clippy::never_loop))]
+    pub(crate) async fn send_messages(
+        &self,
+        messages: Vec<Message>,
+        kind: ErrorKind,
+    ) -> CometdResult<Vec<Message>> {
+        let mut messages = self.apply_outgoing_extensions(messages);
+        if messages.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let ids = messages
+            .iter()
+            .filter_map(|message| message.id.clone())
+            .collect::<Vec<_>>();
+
+        let response = loop {
+            let connection_type = match kind {
+                ErrorKind::Handshake => self.handshake_connection_type(),
+                _ => self.connection_type(),
+            };
+            set_connection_type(&mut messages, connection_type);
+            let messages = messages.as_slice();
+
+            match connection_type {
+                ConnectionType::LongPolling => {
+                    break self.send_http_messages(messages, kind).await?
+                }
+                ConnectionType::CallbackPolling => {
+                    break self.send_callback_messages(messages, kind).await?
+                }
+                #[cfg(feature = "websocket")]
+                ConnectionType::WebSocket => match self.websocket(kind).await {
+                    Ok(websocket) => {
+                        break self
+                            .send_websocket_messages(websocket, messages, kind)
+                            .await?
+                    }
+                    // websocket upgrade can be blocked by proxy,
+                    // temporary network errors are returned as is
+                    Err(ref error)
+                        if is_upgrade_error(error)
+                            && self.fallback_connection_type(ConnectionType::WebSocket) => {}
+                    Err(error) => return Err(error),
+                },
+            }
+        };
+
+        let (replies, pushed) = response
+            .into_iter()
+            .partition::<Vec<_>, _>(|message| message.is_reply_to(&ids));
+        if !pushed.is_empty() {
+            let _ = self.pushed_tx.send(pushed);
+        }
+
+        Ok(self.apply_incoming_extensions(replies))
+    }
+
+    /// Send one message and return server reply to it.
+    #[inline]
+    pub(crate) async fn send_message(
+        &self,
+        message: Message,
+        kind: ErrorKind,
+    ) -> CometdResult<Message> {
+        let id = message.id.clone();
+
+        self.send_messages(vec![message], kind)
+            .await?
+            .into_iter()
+            .find(|reply| reply.id == id)
+            .ok_or_else(|| {
+                CometdError::wrong_response(
+                    kind,
+                    Reconnect::None,
+                    "The response corresponding request id cannot be found.",
+                )
+            })
+    }
+
+    #[inline]
+    async fn send_http_messages(
+        &self,
+        messages: &[Message],
+        kind: ErrorKind,
+    ) -> CometdResult<Vec<Message>> {
+        let body = serde_json::to_string(messages)
+            .map_err(|error| CometdError::SerializeData(kind, error))?;
+
+        let request_builder = self.create_request_builder(self.endpoint().uri(kind));
+        self.send_request_and_parse_json_body(request_builder, body, kind)
+            .await
+    }
+}
+
+/// Set `connectionType` of connect messages to transport which actually sends them.
+#[inline]
+fn set_connection_type(messages: &mut [Message], connection_type: ConnectionType) {
+    for message in messages
+        .iter_mut()
+        .filter(|message| message.channel.as_deref() == Some("/meta/connect"))
+    {
+        message.connection_type = Some(connection_type.as_str().to_owned());
+    }
+}
@@ -22,11 +22,14 @@ impl CometdClientInner {
             .body(body.into())
             .map_err(CometdError::unexpected)?;
 
-        let (parts, body) = timeout(self.request_timeout, self.http_client.request(request))
-            .await
-            .map_err(|_| CometdError::RequestTimeout(kind))?
-            .map_err(|error| CometdError::Request(kind, error))?
-            .into_parts();
+        let (parts, body) = timeout(
+            self.request_timeout(kind),
+            self.http_client.request(request),
+        )
+        .await
+        .map_err(|_| CometdError::RequestTimeout(kind))?
+        .map_err(|error| CometdError::Request(kind, error))?
+        .into_parts();
         let Parts {
             status, headers, ..
         } = parts;
@@ -41,6 +44,20 @@ impl CometdClientInner {
         body: String,
         kind: ErrorKind,
     ) -> CometdResult<R> {
+        let raw_body = self
+            .send_request_and_fetch_body(request_builder, body, kind)
+            .await?;
+
+        serde_json::from_slice::<R>(&raw_body).map_err(|error| CometdError::ParseBody(kind, error))
+    }
+
+    #[inline]
+    pub(crate) async fn send_request_and_fetch_body(
+        &self,
+        request_builder: Builder,
+        body: String,
+        kind: ErrorKind,
+    ) -> CometdResult<Vec<u8>> {
         let (status, headers, body) = self
             .send_request_response(request_builder, body, kind)
             .await?;
@@ -49,10 +66,7 @@ impl CometdClientInner {
         self.extract_and_store_cookie(&headers);
 
         if status.is_success() {
-            let raw_body = body.map_err(|error| CometdError::FetchBody(kind, error))?;
-
-            serde_json::from_slice::<R>(&raw_body)
-                .map_err(|error| CometdError::ParseBody(kind, error))
+            body.map_err(|error| CometdError::FetchBody(kind, error))
         } else {
             Err(CometdError::StatusCode(
                 kind,
@@ -0,0 +1,77 @@
+use crate::{
+    types::{Advice, ChannelId, CometdError, CometdResult, ErrorKind, Message, Reconnect},
+    CometdClientInner,
+};
+
+impl CometdClientInner {
+    /// Send one message per channel and return server verdict for every channel.
+    /// Fail whole request if server advice to retry or to redo handshake.
+    pub(crate) async fn send_subscription_request(
+        &self,
+        meta_channel: &str,
+        subscriptions: &[ChannelId],
+        kind: ErrorKind,
+    ) -> CometdResult<Vec<(ChannelId, CometdResult<()>)>> {
+        let client_id = self
+            .client_id
+            .load_full()
+            .ok_or_else(|| CometdError::MissingClientId(kind))?;
+        let ids = subscriptions
+            .iter()
+            .map(|_| self.next_id())
+            .collect::<Vec<_>>();
+        let messages = subscriptions
+            .iter()
+            .zip(&ids)
+            .map(|(subscription, id)| Message {
+                id: Some(id.clone()),
+                channel: Some(meta_channel.to_owned()),
+                subscription: Some(subscription.to_string()),
+                client_id: Some((*client_id).clone()),
+                ..Default::default()
+            })
+            .collect::<Vec<_>>();
+
+        let mut messages = self.send_messages(messages, kind).await?;
+
+        subscriptions
+            .iter()
+            .zip(ids)
+            .map(|(subscription, id)| {
+                let result = match messages
+                    .iter()
+                    .position(|message| message.id.as_ref() == Some(&id))
+                    .map(|position| messages.swap_remove(position))
+                {
+                    Some(Message {
+                        successful: Some(false),
+                        error,
+                        advice,
+                        ..
+                    }) => match Advice::reconnect(advice) {
+                        Reconnect::None => Err(CometdError::wrong_response(
+                            kind,
+                            Reconnect::None,
+                            error.unwrap_or_default(),
+                        )),
+                        reconnect => {
+                            return Err(CometdError::wrong_response(
+                                kind,
+                                reconnect,
+                                error.unwrap_or_default(),
+                            ))
+                        }
+                    },
+                    Some(_) => Ok(()),
+                    None => Err(CometdError::wrong_response(
+                        kind,
+                        Reconnect::None,
+                        "The response corresponding request id cannot be found.",
+                    )),
+                };
+
+                Ok((subscription.clone(), result))
+            })
+            .collect()
+    }
+}
@@ -0,0 +1,296 @@
+use crate::{
+    types::{
+        AccessToken, CometdError, CometdResult, ErrorKind, Message, Notification,
+        NotificationSender,
+    },
+    CometdClientInner,
+};
+use core::fmt::{self, Debug, Formatter};
+use futures_util::{
+    stream::{SplitSink, SplitStream},
+    SinkExt as _, StreamExt as _,
+};
+use hyper::{
+    header::{AUTHORIZATION, COOKIE},
+    http::HeaderValue,
+};
+use std::{
+    collections::HashMap,
+    sync::{Arc, Mutex, PoisonError},
+};
+use tokio::{
+    net::TcpStream,
+    sync::{mpsc, oneshot},
+    task::JoinHandle,
+    time::timeout,
+};
+use tokio_tungstenite::{
+    connect_async,
+    tungstenite::{client::IntoClientRequest as _, Error as WsError, Message as WsMessage},
+    MaybeTlsStream, WebSocketStream,
+};
+use url::Url;
+
+type WsStream = WebSocketStream<MaybeTlsStream<TcpStream>>;
+type PendingReplies = Arc<Mutex<HashMap<String, oneshot::Sender<Message>>>>;
+
+/// Opened websocket to cometd server.
+/// Server replies are matched to requests by message id, other messages are passed to client task.
+pub(crate) struct WebSocket {
+    url: Url,
+    sink: tokio::sync::Mutex<SplitSink<WsStream, WsMessage>>,
+    pending: PendingReplies,
+    reader: JoinHandle<()>,
+}
+
+impl Debug for WebSocket {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_struct("WebSocket")
+            .field("url", &self.url)
+            .finish_non_exhaustive()
+    }
+}
+
+impl Drop for WebSocket {
+    #[inline(always)]
+    fn drop(&mut self) {
+        self.reader.abort();
+    }
+}
+
+/// Removes pending replies of request when it's finished or cancelled.
+struct PendingGuard<'a> {
+    pending: &'a PendingReplies,
+    ids: Vec<String>,
+}
+
+impl Drop for PendingGuard<'_> {
+    #[inline]
+    fn drop(&mut self) {
+        let mut pending = self.pending.lock().unwrap_or_else(PoisonError::into_inner);
+
+        for id in &self.ids {
+            pending.remove(id);
+        }
+    }
+}
+
+impl WebSocket {
+    async fn send(&self, messages: &[Message], kind: ErrorKind) -> CometdResult<Vec<Message>> {
+        let text = serde_json::to_string(messages)
+            .map_err(|error| CometdError::SerializeData(kind, error))?;
+
+        // replies which won't be waited anymore (e.g. request was cancelled) are forgotten on drop
+        let guard = PendingGuard {
+            pending: &self.pending,
+            ids: messages
+                .iter()
+                .filter_map(|message| message.id.clone())
+                .collect(),
+        };
+        let receivers = {
+            let mut pending = self.pending.lock().unwrap_or_else(PoisonError::into_inner);
+
+            guard
+                .ids
+                .iter()
+                .map(|id| {
+                    let (reply_tx, reply_rx) = oneshot::channel();
+                    pending.insert(id.clone(), reply_tx);
+                    reply_rx
+                })
+                .collect::<Vec<_>>()
+        };
+
+        self.sink
+            .lock()
+            .await
+            .send(WsMessage::Text(text))
+            .await
+            .map_err(|error| CometdError::WebSocket(kind, Box::new(error)))?;
+
+        let mut replies = Vec::with_capacity(receivers.len());
+        for reply_rx in receivers {
+            let reply = reply_rx
+                .await
+                .map_err(|_| CometdError::WebSocket(kind, Box::new(WsError::ConnectionClosed)))?;
+            replies.push(reply);
+        }
+        drop(guard);
+
+        Ok(replies)
+    }
+
+    fn is_closed(&self) -> bool {
+        self.reader.is_finished()
+    }
+}
+
+impl CometdClientInner {
+    pub(crate) async fn send_websocket_messages(
+        &self,
+        websocket: Arc<WebSocket>,
+        messages: &[Message],
+        kind: ErrorKind,
+    ) -> CometdResult<Vec<Message>> {
+        let result = timeout(self.request_timeout(kind), websocket.send(messages, kind))
+            .await
+            .unwrap_or(Err(CometdError::RequestTimeout(kind)));
+
+        if matches!(result, Err(ref error) if error.is_transport()) {
+            let mut current = self.websocket.lock().await;
+            if current
+                .as_ref()
+                .is_some_and(|current| Arc::ptr_eq(current, &websocket))
+            {
+                *current = None;
+            }
+        }
+
+        result
+    }
+
+    /// Close websocket, next request opens new one.
+    #[inline]
+    pub(crate) async fn close_websocket(&self) {
+        if let Some(websocket) = self.websocket.lock().await.take() {
+            let _ = websocket.sink.lock().await.close().await;
+        }
+    }
+
+    /// Return opened websocket to current server or open new one.
+    pub(crate) async fn websocket(&self, kind: ErrorKind) -> CometdResult<Arc<WebSocket>> {
+        let url = self.endpoint().websocket.clone();
+        let mut current = self.websocket.lock().await;
+
+        match current.as_ref() {
+            Some(websocket) if websocket.url == url && !websocket.is_closed() => {
+                Ok(Arc::clone(websocket))
+            }
+            _ => {
+                let websocket = Arc::new(self.open_websocket(url, kind).await?);
+                *current = Some(Arc::clone(&websocket));
+
+                Ok(websocket)
+            }
+        }
+    }
+
+    async fn open_websocket(&self, url: Url, kind: ErrorKind) -> CometdResult<WebSocket> {
+        let ws_error = |error| CometdError::WebSocket(kind, Box::new(error));
+
+        let mut request = url.as_str().into_client_request().map_err(ws_error)?;
+        let headers = request.headers_mut();
+
+        // set authorization header
+        if let Some(token) = self
+            .access_token
+            .load()
+            .as_deref()
+            .map(Box::as_ref)
+            .map(AccessToken::get_authorization_token)
+        {
+            headers.insert(
+                AUTHORIZATION,
+                HeaderValue::from_str(token).map_err(CometdError::unexpected)?,
+            );
+        }
+
+        // set cookies
+        if let Some(cookies) = self.cookies_string_cache.load().as_deref().map(Box::as_ref) {
+            headers.insert(
+                COOKIE,
+                HeaderValue::from_str(cookies).map_err(CometdError::unexpected)?,
+            );
+        }
+
+        let (stream, response) = timeout(self.request_timeout(kind), connect_async(request))
+            .await
+            .map_err(|_| CometdError::RequestTimeout(kind))?
+            .map_err(ws_error)?;
+
+        self.extract_and_store_cookie(response.headers());
+
+        let (sink, stream) = stream.split();
+        let pending = PendingReplies::default();
+        let reader = tokio::spawn(read(
+            stream,
+            Arc::clone(&pending),
+            self.pushed_tx.clone(),
+            self.notification_tx.clone(),
+        ));
+
+        Ok(WebSocket {
+            url,
+            sink: tokio::sync::Mutex::new(sink),
+            pending,
+            reader,
+        })
+    }
+}
+
+/// Check if websocket can't be opened at all (e.g. upgrade is refused by server or proxy),
+/// unlike temporary network errors.
+pub(crate) fn is_upgrade_error(error: &CometdError) -> bool {
+    match *error {
+        CometdError::WebSocket(_, ref error) => matches!(
+            **error,
+            WsError::Http(_) | WsError::HttpFormat(_) | WsError::Protocol(_) | WsError::Url(_)
+        ),
+        _ => false,
+    }
+}
+
+/// Read messages from websocket until it's closed, frames which can't be parsed are reported as errors.
+async fn read(
+    mut stream: SplitStream<WsStream>,
+    pending: PendingReplies,
+    pushed_tx: mpsc::UnboundedSender<Vec<Message>>,
+    notification_tx: NotificationSender,
+) {
+    while let Some(Ok(frame)) = stream.next().await {
+        let messages = match frame {
+            WsMessage::Text(text) => serde_json::from_str::<Vec<Message>>(&text),
+            WsMessage::Binary(bytes) => serde_json::from_slice::<Vec<Message>>(&bytes),
+            WsMessage::Close(_) => break,
+            WsMessage::Ping(_) | WsMessage::Pong(_) | WsMessage::Frame(_) => continue,
+        };
+        let messages = match messages {
+            Ok(messages) => messages,
+            Err(error) => {
+                let error = CometdError::ParseBody(ErrorKind::Connect, error);
+                let _ = notification_tx.send(Notification::Error(error));
+                continue;
+            }
+        };
+
+        let mut pushed = Vec::new();
+        {
+            let mut pending = pending.lock().unwrap_or_else(PoisonError::into_inner);
+
+            for message in messages {
+                match message
+                    .id
+                    .as_ref()
+                    .filter(|_| message.successful.is_some())
+                    .and_then(|id| pending.remove(id))
+                {
+                    Some(reply_tx) => {
+                        let _ = reply_tx.send(message);
+                    }
+                    None => pushed.push(message),
+                }
+            }
+        }
+
+        if !pushed.is_empty() {
+            let _ = pushed_tx.send(pushed);
+        }
+    }
+
+    // wake up everyone who waits for reply
+    pending
+        .lock()
+        .unwrap_or_else(PoisonError::into_inner)
+        .clear();
+}
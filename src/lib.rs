@@ -34,6 +34,7 @@
 //!
 //! # Table of contents
 //! - [Connect endpoints](#connect-endpoints)
+//! - [Connection type](#connection-type)
 //! - [Authentication](#authentication)
 //! - [Authentication through authorization header](#authorization-authentication)
 //! - [Authentication through cookie](#cookie-authentication)
@@ -46,6 +47,7 @@
 //! 2) [`CometdClientBuilder::subscribe_base_path`];
 //! 3) [`CometdClientBuilder::connect_base_path`];
 //! 4) [`CometdClientBuilder::disconnect_base_path`];
+//! 5) [`CometdClientBuilder::publish_base_path`];
 //!
 //! For example to change handshake base path and
 //! get `http://[::1]:1025/notifications/node/0/handshake`
@@ -64,6 +66,26 @@
 //!
 //! Same for others endpoints.
 //!
+//! To use several cometd servers, construct builder through [`CometdClientBuilder::with_endpoints`].
+//! Client switches to next server on handshake or transport errors,
+//! servers from `advice.hosts` are used too.
+//! Server which is currently used can be got through [`CometdClient::endpoint`].
+//!
+//! # Connection type
+//!
+//! By default client uses `long-polling`.
+//! If proxy blocks POST requests, then `callback-polling` can be used:
+//! messages are sent in query of GET requests and server replies are unwrapped from JSONP.
+//! With `websocket` feature client can use websocket:
+//! handshake is still sent with HTTP request, then connect, subscribe and publish requests
+//! go through one websocket which is opened at server base url.
+//!
+//! Preferred connection types are set through [`CometdClientBuilder::connection_types`],
+//! client picks first one which server supports and falls back to next one
+//! if websocket upgrade is refused (temporary network errors don't cause fallback).
+//! Fallback is kept until next handshake.
+//! Connection type which is currently used can be got through [`CometdClient::connection_type`].
+//!
 //! # Authentication
 //!
 //! There is 2 options to authenticate on server,
@@ -142,18 +164,77 @@
 //! [`CometdClientBuilder::build`] spawn task which do handshake and start wait for messages.
 //! If handshake request was unsuccessful with [`types::Reconnect::Handshake`] or [`types::Reconnect::Retry`] advice from server,
 //! then client tries redo it by [`CometdClientBuilder::number_of_retries`] times.
+//! Delay between retries is set by [`CometdClientBuilder::backoff`].
 //! In other cases task send error to event channel and stops.
+//! To keep client alive through network and server errors use [`CometdClientBuilder::supervised`].
 //!
 //! After successful handshake task start listen messages coming from server.
+//! Client follows `interval` and `timeout` advices from server: it waits `interval` between
+//! connect requests and prolongs connect request timeout by `timeout`.
+//! If server advices `reconnect: none`, then client stops without disconnect request.
 //! If during that requests occurs error with [`types::Reconnect::Handshake`] advice,
 //! then client will tries redo handshake (look above).
+//! After every new handshake client resubscribes to all active subscriptions,
+//! if server denies some of them, then [`types::CometdError::Resubscribe`] is sent to event channel.
 //! If error will be with [`types::Reconnect::Retry`] advice, then it will try redo it
 //! by [`CometdClientBuilder::number_of_retries`] times.
 //!
-//! To send subscribe command you must use [`CometdClient::subscribe`].
+//! This is behaviour of default [`types::retry_policy::AdviceRetry`] policy,
+//! it can be replaced through [`CometdClientBuilder::retry_policy`].
+//!
+//! Channel names are validated by [`types::ChannelId`] before request is sent,
+//! so invalid names (e.g. `/foo//bar` or `meta/x`) fail with [`types::CometdError::InvalidChannel`].
+//!
+//! To subscribe to channel you must use [`CometdClient::subscribe`].
 //! If error occurs it will be redone by same scheme as for connect (look above).
+//! It returns [`types::Subscription`] handle which yields messages of channel,
+//! denied channel returns error and doesn't stop client.
+//! Handles to same channel share one server subscription and client unsubscribes
+//! from channel when the last handle is dropped.
+//!
+//! If messages are read from event channel only, use [`CometdClient::subscribe_batch`]
+//! and [`CometdClient::unsubscribe`]: they return server verdict for every channel
+//! and don't keep handles.
+//!
+//! If channels carry different payloads use [`CometdClient::subscribe_typed`]:
+//! every typed subscription parses messages of its channel by itself
+//! and they aren't sent to event channel.
+//!
+//! Message type is parsed through [`types::CometdMessage`] which is implemented
+//! for every deserializable type. With `derive` feature enum can derive it, so `data` is parsed
+//! into variant picked by `#[cometd(channel = "...")]` pattern instead of guessing by payload shape.
+//!
+//! Channels which client is subscribed to can be got through [`CometdClient::subscriptions`].
+//!
+//! Instead of receiving messages by hand, handler can be registered through [`CometdClient::route`]:
+//! its arguments are extracted from message (e.g. [`types::router::Json`] or [`types::ChannelId`]),
+//! errors and panics of handler are sent to event channel and don't stop delivery.
+//!
+//! To publish message to channel you must use [`CometdClient::publish`].
+//! It returns server reply, retries are done by same scheme as for subscribe.
+//!
+//! Remote calls to `/service/**` channels are done through [`CometdClient::call`]:
+//! server reply is matched by message id and returned to caller instead of event channel.
+//!
+//! Bayeux extensions can be registered through [`CometdClientBuilder::extension`],
+//! they can change (e.g. fill `ext` field) or drop every sent and received message,
+//! look at [`types::Extension`].
+//! Crate provides acknowledgement extension (look at [`CometdClientBuilder::ack_extension`])
+//! for reliable message delivery and timesync extension (look at [`CometdClientBuilder::timesync_extension`])
+//! for estimation of server clock offset.
+//! [`types::extension::ReplayExtension`] resubscribes from last received replay id,
+//! which can be kept in memory or in file (look at [`types::extension::ReplayStore`]).
+//!
+//! Session can be saved through [`CometdClient::into_snapshot`] (e.g. before process restart)
+//! and resumed through [`CometdClientBuilder::resume`]: client sends connect request with previous
+//! client id and does handshake with resubscription only if server rejects it.
 //!
 //! To get event channel receiver use [`CometdClient::rx`].
+//! To receive only messages from channels matching wildcard pattern
+//! (e.g. `/orders/**` or `/prices/*`) use [`CometdClient::rx_matching`].
+//! Receivers and subscription handles are also `futures_core::Stream`s,
+//! [`types::CometdEventReceiver::messages`] and [`types::CometdEventReceiver::errors`]
+//! turn event channel into stream of single messages or errors.
 //!
 //! ```rust,no_run
 //! use cometd_client::{types::CometdClientEvent, CometdClientBuilder};
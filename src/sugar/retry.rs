@@ -1,8 +1,13 @@
-use crate::types::{CometdError, CometdResult, ErrorKind, Reconnect};
+use crate::{
+    types::{retry_policy::RetryDecision, CometdResult, ErrorKind},
+    CometdClientInner,
+};
 use core::future::Future;
+use tokio::time::sleep;
 
 pub(crate) async fn retry_with_advice<T, Fut, HandshakeFut>(
-    number_of_retries: usize,
+    inner: &CometdClientInner,
+    kind: ErrorKind,
     handshake: impl Fn() -> HandshakeFut,
     f: impl Fn() -> Fut,
 ) -> CometdResult<T>
@@ -10,52 +15,51 @@ where
     Fut: Future<Output = CometdResult<T>>,
     HandshakeFut: Future<Output = CometdResult<()>>,
 {
-    let mut f_retries = number_of_retries;
+    let mut attempt = 0;
 
     loop {
         match f().await {
             Ok(ret) => break Ok(ret),
-            Err(CometdError::WrongResponse(kind, Reconnect::Handshake, _)) => {
-                check_retries(kind, f_retries)?;
-                handshake_retry(number_of_retries, &handshake).await?;
-            }
-            Err(CometdError::WrongResponse(kind, Reconnect::Retry, _)) => {
-                check_retries(kind, f_retries)?
-            }
-            Err(error) => break Err(error),
+            Err(error) => match inner.retry_policy.decide(&error, kind, attempt) {
+                RetryDecision::Retry(delay) => {
+                    inner.failover_on_error(&error);
+                    sleep(delay).await;
+                }
+                RetryDecision::Handshake(delay) => {
+                    inner.failover_on_error(&error);
+                    sleep(delay).await;
+                    handshake_retry(inner, &handshake).await?;
+                }
+                RetryDecision::GiveUp => break Err(error),
+            },
         }
-        f_retries -= 1;
+        attempt += 1;
     }
 }
 
 #[inline(always)]
 async fn handshake_retry<HandshakeFut>(
-    mut handshake_retries: usize,
+    inner: &CometdClientInner,
     handshake: impl Fn() -> HandshakeFut,
 ) -> CometdResult<()>
 where
     HandshakeFut: Future<Output = CometdResult<()>>,
 {
+    const KIND: ErrorKind = ErrorKind::Handshake;
+
+    let mut attempt = 0;
+
     loop {
         match handshake().await {
             Ok(()) => break Ok(()),
-            Err(CometdError::WrongResponse(kind, Reconnect::Handshake | Reconnect::Retry, _)) => {
-                check_retries(kind, handshake_retries)?
-            }
-            Err(error) => break Err(error),
+            Err(error) => match inner.retry_policy.decide(&error, KIND, attempt) {
+                RetryDecision::Retry(delay) | RetryDecision::Handshake(delay) => {
+                    inner.failover_on_error(&error);
+                    sleep(delay).await;
+                }
+                RetryDecision::GiveUp => break Err(error),
+            },
         }
-        handshake_retries -= 1;
+        attempt += 1;
     }
 }
-
-#[inline(always)]
-fn check_retries(kind: ErrorKind, retries: usize) -> CometdResult<()> {
-    retries
-        .gt(&0)
-        .then_some(())
-        .ok_or(CometdError::wrong_response(
-            kind,
-            Reconnect::None,
-            "exhausted attempts",
-        ))
-}
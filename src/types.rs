@@ -1,16 +1,42 @@
+mod backoff;
 mod channel;
+mod channel_id;
+mod channel_pattern;
+mod cometd_message;
 mod command;
+mod connection_type;
 mod error;
 mod event;
 mod message;
+mod session_snapshot;
 
 /// Contains different implementations for `AccessToken` trait.
 pub mod access_token;
+/// Contains different implementations for `Extension` trait.
+pub mod extension;
+/// Contains different implementations for `RetryPolicy` trait.
+pub mod retry_policy;
+/// Contains extractors and handler traits for `CometdClient::route`.
+pub mod router;
+
+/// Items used by `#[derive(CometdMessage)]`, not public API.
+#[doc(hidden)]
+pub mod __private {
+    pub use super::{channel_id::validate_channel, cometd_message::parse_data};
+    pub use serde_json::Value as JsonValue;
+}
 
 use tokio::sync::mpsc;
 
 pub(crate) use command::*;
-pub use {access_token::AccessToken, channel::*, error::*, event::*, message::*};
+pub use {
+    access_token::AccessToken, backoff::*, channel::*, channel_id::ChannelId, channel_pattern::*,
+    cometd_message::*, connection_type::*, error::*, event::*, extension::Extension, message::*,
+    retry_policy::RetryPolicy, session_snapshot::*,
+};
+
+#[cfg(feature = "derive")]
+pub use cometd_client_derive::CometdMessage;
 
 pub(crate) type InactiveEventReceiver<Msg> =
     async_broadcast::InactiveReceiver<CometdClientEvent<Msg>>;
@@ -18,3 +44,5 @@ pub(crate) type EventSender<Msg> = async_broadcast::Sender<CometdClientEvent<Msg
 
 pub(crate) type CmdReceiver = mpsc::Receiver<Command>;
 pub(crate) type CmdSender = mpsc::Sender<Command>;
+pub(crate) type NotificationReceiver = mpsc::UnboundedReceiver<Notification>;
+pub(crate) type NotificationSender = mpsc::UnboundedSender<Notification>;
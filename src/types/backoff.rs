@@ -0,0 +1,144 @@
+use crate::consts::{DEFAULT_BACKOFF_INITIAL_DELAY, DEFAULT_BACKOFF_MAX_DELAY};
+use core::time::Duration;
+
+/// Randomization which is applied to [`Backoff`] delays.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum Jitter {
+    /// Use exponential delay as is.
+    None,
+    /// Pick random delay between zero and exponential delay.
+    #[default]
+    Full,
+    /// Pick random delay between initial delay and tripled previous delay.
+    Decorrelated,
+}
+
+/// Exponential backoff strategy which is used between retries.
+///
+/// # Example
+/// ```rust
+/// # use cometd_client::types::{Backoff, Jitter};
+/// # use core::time::Duration;
+/// let backoff = Backoff::new(Duration::from_millis(100), Duration::from_secs(10))
+///     .multiplier(3.0)
+///     .jitter(Jitter::None);
+///
+/// let delays = backoff.iter().take(3).collect::<Vec<_>>();
+/// assert_eq!(delays, [Duration::from_millis(100), Duration::from_millis(300), Duration::from_millis(900)]);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Backoff {
+    initial_delay: Duration,
+    multiplier: f64,
+    max_delay: Duration,
+    jitter: Jitter,
+}
+
+impl Default for Backoff {
+    #[inline(always)]
+    fn default() -> Self {
+        Self::new(DEFAULT_BACKOFF_INITIAL_DELAY, DEFAULT_BACKOFF_MAX_DELAY)
+    }
+}
+
+impl Backoff {
+    /// Construct a new `Backoff` with doubling delay and full jitter.
+    #[inline(always)]
+    pub const fn new(initial_delay: Duration, max_delay: Duration) -> Self {
+        Self {
+            initial_delay,
+            multiplier: 2.0,
+            max_delay,
+            jitter: Jitter::Full,
+        }
+    }
+
+    /// Set multiplier of delay for every next attempt.
+    #[inline(always)]
+    #[must_use]
+    pub const fn multiplier(mut self, multiplier: f64) -> Self {
+        self.multiplier = multiplier;
+        self
+    }
+
+    /// Set jitter.
+    #[inline(always)]
+    #[must_use]
+    pub const fn jitter(mut self, jitter: Jitter) -> Self {
+        self.jitter = jitter;
+        self
+    }
+
+    /// Return endless iterator over delays for consecutive attempts.
+    #[inline(always)]
+    pub const fn iter(&self) -> BackoffIter {
+        BackoffIter {
+            backoff: *self,
+            attempt: 0,
+            previous_delay: Duration::ZERO,
+        }
+    }
+
+    /// Return delay for given attempt (starts from zero).
+    #[inline]
+    pub fn delay(&self, attempt: usize) -> Duration {
+        self.iter().nth(attempt).unwrap_or(self.max_delay)
+    }
+
+    #[inline]
+    fn exponential_delay(&self, attempt: i32) -> Duration {
+        let delay = self.initial_delay.as_secs_f64() * self.multiplier.powi(attempt);
+
+        Duration::try_from_secs_f64(delay)
+            .unwrap_or(self.max_delay)
+            .min(self.max_delay)
+    }
+}
+
+/// Iterator over [`Backoff`] delays.
+#[derive(Debug, Clone)]
+pub struct BackoffIter {
+    backoff: Backoff,
+    attempt: i32,
+    previous_delay: Duration,
+}
+
+impl Iterator for BackoffIter {
+    type Item = Duration;
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        let Backoff {
+            initial_delay,
+            max_delay,
+            jitter,
+            ..
+        } = self.backoff;
+
+        let delay = match jitter {
+            Jitter::None => self.backoff.exponential_delay(self.attempt),
+            Jitter::Full => {
+                random_between(Duration::ZERO, self.backoff.exponential_delay(self.attempt))
+            }
+            Jitter::Decorrelated => random_between(
+                initial_delay,
+                self.previous_delay.saturating_mul(3).max(initial_delay),
+            )
+            .min(max_delay),
+        };
+
+        self.attempt = self.attempt.saturating_add(1);
+        self.previous_delay = delay;
+
+        Some(delay)
+    }
+}
+
+#[inline]
+fn random_between(low: Duration, high: Duration) -> Duration {
+    let to_nanos = |duration: Duration| u64::try_from(duration.as_nanos()).unwrap_or(u64::MAX);
+
+    Duration::from_nanos(fastrand::u64(
+        to_nanos(low)..=to_nanos(high).max(to_nanos(low)),
+    ))
+}
@@ -1,3 +1,7 @@
+mod error_stream;
+mod matching_receiver;
+mod message_stream;
 mod receiver;
+mod subscription;
 
-pub use receiver::*;
+pub use {error_stream::*, matching_receiver::*, message_stream::*, receiver::*, subscription::*};
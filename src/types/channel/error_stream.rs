@@ -0,0 +1,41 @@
+use crate::types::{CometdClientEvent, CometdError, CometdEventReceiver};
+use core::{
+    future::poll_fn,
+    pin::Pin,
+    task::{ready, Context, Poll},
+};
+use futures_core::Stream;
+use std::sync::Arc;
+
+/// Stream of errors from event channel, messages are skipped,
+/// look at [`CometdEventReceiver::errors`].
+#[derive(Debug)]
+pub struct CometdErrorStream<Msg>(CometdEventReceiver<Msg>);
+
+impl<Msg> CometdErrorStream<Msg> {
+    #[inline(always)]
+    pub(crate) const fn new(rx: CometdEventReceiver<Msg>) -> Self {
+        Self(rx)
+    }
+
+    /// Receive next error.
+    /// Return `None` if channel was closed.
+    #[inline(always)]
+    pub async fn recv(&mut self) -> Option<Arc<CometdError>> {
+        poll_fn(|cx| Pin::new(&mut *self).poll_next(cx)).await
+    }
+}
+
+impl<Msg> Stream for CometdErrorStream<Msg> {
+    type Item = Arc<CometdError>;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        loop {
+            match ready!(Pin::new(&mut self.0).poll_next(cx)) {
+                Some(CometdClientEvent::Error(error)) => return Poll::Ready(Some(error)),
+                Some(CometdClientEvent::Message(_)) => {}
+                None => return Poll::Ready(None),
+            }
+        }
+    }
+}
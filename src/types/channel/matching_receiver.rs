@@ -0,0 +1,96 @@
+use crate::types::{ChannelPattern, CometdEventReceiver, CometdMessageStream, Data};
+use core::{
+    future::poll_fn,
+    ops::Deref,
+    pin::Pin,
+    task::{ready, Context, Poll},
+};
+use futures_core::Stream;
+use std::sync::Arc;
+
+/// Event channel receiver which yields only messages from channels matching pattern,
+/// look at [`crate::CometdClient::rx_matching`].
+///
+/// It's also [`Stream`] of matched messages.
+#[derive(Debug)]
+pub struct CometdMatchingReceiver<Msg> {
+    messages: CometdMessageStream<Msg>,
+    pattern: ChannelPattern,
+}
+
+/// Message from received batch, it can be used as [`Data`].
+#[derive(Debug)]
+pub struct MatchedData<Msg> {
+    batch: Arc<[Data<Msg>]>,
+    index: usize,
+}
+
+impl<Msg> CometdMatchingReceiver<Msg> {
+    #[inline(always)]
+    pub(crate) const fn new(rx: CometdEventReceiver<Msg>, pattern: ChannelPattern) -> Self {
+        Self {
+            messages: CometdMessageStream::new(rx),
+            pattern,
+        }
+    }
+
+    /// Return pattern which channels are matched with.
+    #[inline(always)]
+    pub const fn pattern(&self) -> &ChannelPattern {
+        &self.pattern
+    }
+
+    /// Receive next message from matching channel, errors are skipped.
+    /// Return `None` if channel was closed.
+    #[inline(always)]
+    pub async fn recv(&mut self) -> Option<MatchedData<Msg>> {
+        poll_fn(|cx| Pin::new(&mut *self).poll_next(cx)).await
+    }
+}
+
+impl<Msg> Stream for CometdMatchingReceiver<Msg> {
+    type Item = MatchedData<Msg>;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let this = &mut *self;
+
+        loop {
+            match ready!(this.messages.poll_next_shared(cx)) {
+                Some(data)
+                    if !data
+                        .channel
+                        .as_deref()
+                        .is_some_and(|channel| this.pattern.matches(channel)) => {}
+                data => return Poll::Ready(data),
+            }
+        }
+    }
+}
+
+impl<Msg> MatchedData<Msg> {
+    /// `index` must be less than `batch` length.
+    #[inline(always)]
+    pub(crate) const fn new(batch: Arc<[Data<Msg>]>, index: usize) -> Self {
+        Self { batch, index }
+    }
+}
+
+impl<Msg> Deref for MatchedData<Msg> {
+    type Target = Data<Msg>;
+
+    #[inline(always)]
+    #[allow(clippy::indexing_slicing)] // index is checked by message stream
+    fn deref(&self) -> &Self::Target {
+        &self.batch[self.index]
+    }
+}
+
+// derive requires `Msg: Clone`
+impl<Msg> Clone for MatchedData<Msg> {
+    fn clone(&self) -> Self {
+        Self {
+            batch: Arc::clone(&self.batch),
+            index: self.index,
+        }
+    }
+}
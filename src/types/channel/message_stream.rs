@@ -0,0 +1,76 @@
+use crate::types::{CometdClientEvent, CometdEventReceiver, Data, MatchedData};
+use core::{
+    future::poll_fn,
+    pin::Pin,
+    task::{ready, Context, Poll},
+};
+use futures_core::Stream;
+use std::sync::Arc;
+
+/// Stream of single messages from event channel, errors are skipped,
+/// look at [`CometdEventReceiver::messages`].
+///
+/// Messages are copied out of received batches, use [`crate::CometdClient::rx_matching`]
+/// to get them without copying.
+#[derive(Debug)]
+pub struct CometdMessageStream<Msg> {
+    rx: CometdEventReceiver<Msg>,
+    batch: Option<Arc<[Data<Msg>]>>,
+    index: usize,
+}
+
+impl<Msg> CometdMessageStream<Msg> {
+    #[inline(always)]
+    pub(crate) const fn new(rx: CometdEventReceiver<Msg>) -> Self {
+        Self {
+            rx,
+            batch: None,
+            index: 0,
+        }
+    }
+
+    /// Poll next message without copying it out of received batch.
+    pub(crate) fn poll_next_shared(
+        &mut self,
+        cx: &mut Context<'_>,
+    ) -> Poll<Option<MatchedData<Msg>>> {
+        loop {
+            if let Some(ref batch) = self.batch {
+                if self.index < batch.len() {
+                    let data = MatchedData::new(Arc::clone(batch), self.index);
+                    self.index += 1;
+                    return Poll::Ready(Some(data));
+                }
+            }
+
+            self.batch = None;
+            match ready!(Pin::new(&mut self.rx).poll_next(cx)) {
+                Some(CometdClientEvent::Message(batch)) => {
+                    self.batch = Some(batch);
+                    self.index = 0;
+                }
+                Some(CometdClientEvent::Error(_)) => {}
+                None => return Poll::Ready(None),
+            }
+        }
+    }
+}
+
+impl<Msg: Clone> CometdMessageStream<Msg> {
+    /// Receive next message.
+    /// Return `None` if channel was closed.
+    #[inline(always)]
+    pub async fn recv(&mut self) -> Option<Data<Msg>> {
+        poll_fn(|cx| Pin::new(&mut *self).poll_next(cx)).await
+    }
+}
+
+impl<Msg: Clone> Stream for CometdMessageStream<Msg> {
+    type Item = Data<Msg>;
+
+    #[inline]
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        self.poll_next_shared(cx)
+            .map(|data| data.map(|data| (*data).clone()))
+    }
+}
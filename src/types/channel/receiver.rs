@@ -1,7 +1,15 @@
-use crate::types::CometdClientEvent;
+use crate::types::{CometdClientEvent, CometdErrorStream, CometdMessageStream};
 use async_broadcast::{Receiver, RecvError};
+use core::{
+    pin::Pin,
+    task::{Context, Poll},
+};
+use futures_core::Stream;
 
 /// Event channel receiver.
+///
+/// It's also [`Stream`] of events, look at [`CometdEventReceiver::messages`]
+/// and [`CometdEventReceiver::errors`] for streams of single messages and errors.
 #[derive(Debug)]
 pub struct CometdEventReceiver<Msg>(pub(crate) Receiver<CometdClientEvent<Msg>>);
 
@@ -16,4 +24,40 @@ impl<Msg> CometdEventReceiver<Msg> {
             Err(RecvError::Overflowed(_)) => unreachable!(),
         }
     }
+
+    /// Convert receiver into stream of single messages, batches are flattened and errors are skipped.
+    /// Stream yields [`crate::types::Data`] once `Msg` is `Clone`.
+    ///
+    /// # Example
+    /// ```rust,no_run
+    /// # use cometd_client::CometdClientBuilder;
+    /// # async fn _fun() {
+    /// #   let client = CometdClientBuilder::new(&"http://[::1]:1025/".parse().unwrap()).build::<()>().unwrap();
+    ///     let mut messages = client.rx().messages();
+    ///     let _topic = client.subscribe("/topic0").await;
+    ///
+    ///     while let Some(data) = messages.recv().await {
+    ///         println!("Got message from `{:?}`: `{:?}`.", data.channel, data.message);
+    ///     }
+    /// # }
+    /// ```
+    #[inline(always)]
+    pub const fn messages(self) -> CometdMessageStream<Msg> {
+        CometdMessageStream::new(self)
+    }
+
+    /// Convert receiver into stream of errors, messages are skipped.
+    #[inline(always)]
+    pub const fn errors(self) -> CometdErrorStream<Msg> {
+        CometdErrorStream::new(self)
+    }
+}
+
+impl<Msg> Stream for CometdEventReceiver<Msg> {
+    type Item = CometdClientEvent<Msg>;
+
+    #[inline(always)]
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        Pin::new(&mut self.0).poll_next(cx)
+    }
 }
@@ -0,0 +1,90 @@
+use crate::{
+    types::{parse_message, ChannelId, CometdMessage, CometdResult, Data, Notification},
+    CometdClientInner, RouteReceiver,
+};
+use core::{
+    future::poll_fn,
+    marker::PhantomData,
+    pin::Pin,
+    task::{Context, Poll},
+};
+use futures_core::Stream;
+use std::sync::Arc;
+
+/// Handle of channel subscription which yields messages parsed as `T`,
+/// look at [`crate::CometdClient::subscribe`] and [`crate::CometdClient::subscribe_typed`].
+///
+/// Handles to same channel share one server subscription,
+/// client sends unsubscribe request when the last of them is dropped.
+/// New messages are dropped while handle is full, look at
+/// [`crate::CometdClientBuilder::subscription_channel_capacity`].
+///
+/// It's also [`Stream`] of parsed messages.
+#[derive(Debug)]
+pub struct Subscription<T> {
+    rx: RouteReceiver,
+    guard: SubscriptionGuard,
+    _message: PhantomData<fn() -> T>,
+}
+
+/// Counted reference to server subscription.
+#[derive(Debug)]
+pub(crate) struct SubscriptionGuard {
+    inner: Arc<CometdClientInner>,
+    channel: ChannelId,
+}
+
+impl<T> Subscription<T> {
+    #[inline(always)]
+    pub(crate) const fn new(rx: RouteReceiver, guard: SubscriptionGuard) -> Self {
+        Self {
+            rx,
+            guard,
+            _message: PhantomData,
+        }
+    }
+
+    /// Return subscribed channel.
+    #[inline(always)]
+    pub const fn channel(&self) -> &ChannelId {
+        &self.guard.channel
+    }
+}
+
+impl<T: CometdMessage> Subscription<T> {
+    /// Receive next message from subscribed channel and parse it as `T`.
+    /// Return `None` if client was closed.
+    #[inline(always)]
+    pub async fn recv(&mut self) -> Option<CometdResult<Data<T>>> {
+        poll_fn(|cx| Pin::new(&mut *self).poll_next(cx)).await
+    }
+}
+
+impl<T: CometdMessage> Stream for Subscription<T> {
+    type Item = CometdResult<Data<T>>;
+
+    #[inline]
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        self.rx.poll_recv(cx).map(|data| {
+            let Data { channel, message } = data?;
+
+            Some(parse_message(channel.as_ref(), message).map(|message| Data { channel, message }))
+        })
+    }
+}
+
+impl SubscriptionGuard {
+    /// Wrap handle which was acquired by client task.
+    #[inline(always)]
+    pub(crate) const fn new(inner: Arc<CometdClientInner>, channel: ChannelId) -> Self {
+        Self { inner, channel }
+    }
+}
+
+impl Drop for SubscriptionGuard {
+    #[inline(always)]
+    fn drop(&mut self) {
+        self.inner
+            .notify(Notification::Released(self.channel.clone()));
+    }
+}
@@ -0,0 +1,225 @@
+use crate::types::{ChannelPattern, CometdError, CometdResult};
+use core::{
+    fmt::{self, Display, Formatter},
+    ops::Deref,
+    str::FromStr,
+};
+use serde::{de, Deserialize, Deserializer, Serialize, Serializer};
+
+/// Validated Bayeux channel name.
+///
+/// Name consists of `/`-separated non-empty segments without ASCII whitespace and control characters,
+/// other characters are allowed as servers do (e.g. `/topic/Order:Created`).
+/// Last segment can be wildcard: `*` matches one segment, `**` matches many.
+/// Channels under `/meta/` and `/service/` are meta and service channels, others are broadcast.
+///
+/// # Example
+/// ```rust
+/// # use cometd_client::types::ChannelId;
+/// let channel = "/orders/**".parse::<ChannelId>().unwrap();
+///
+/// assert!(channel.is_wildcard());
+/// assert!(channel.is_broadcast());
+/// assert_eq!(channel.segments().collect::<Vec<_>>(), ["orders", "**"]);
+///
+/// assert!("/foo//bar".parse::<ChannelId>().is_err());
+/// assert!("meta/x".parse::<ChannelId>().is_err());
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct ChannelId(Box<str>);
+
+impl ChannelId {
+    /// Parse and validate channel name.
+    #[inline]
+    pub fn new(channel: impl AsRef<str> + Into<Box<str>>) -> CometdResult<Self> {
+        match validate_channel(channel.as_ref()) {
+            Ok(()) => Ok(Self(channel.into())),
+            Err(reason) => Err(CometdError::InvalidChannel(channel.into(), reason)),
+        }
+    }
+
+    /// Return channel name as string.
+    #[inline(always)]
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+
+    /// Return iterator over channel segments.
+    #[inline]
+    pub fn segments(&self) -> impl Iterator<Item = &str> {
+        self.0.split('/').skip(1)
+    }
+
+    /// Check if last segment is `*` or `**`.
+    #[inline]
+    pub fn is_wildcard(&self) -> bool {
+        self.0.ends_with("/*") || self.0.ends_with("/**")
+    }
+
+    /// Check if channel is under `/meta/`.
+    #[inline]
+    pub fn is_meta(&self) -> bool {
+        self.segments().next() == Some("meta")
+    }
+
+    /// Check if channel is under `/service/`.
+    #[inline]
+    pub fn is_service(&self) -> bool {
+        self.segments().next() == Some("service")
+    }
+
+    /// Check if channel is neither meta nor service one.
+    #[inline]
+    pub fn is_broadcast(&self) -> bool {
+        !self.is_meta() && !self.is_service()
+    }
+
+    /// Check if channel is matched by this one (look at [`ChannelPattern`]).
+    #[inline]
+    pub fn matches(&self, channel: &str) -> bool {
+        ChannelPattern::new(self.as_str()).matches(channel)
+    }
+
+    /// Check that messages can be published to channel.
+    #[inline]
+    pub(crate) fn check_publishable(self) -> CometdResult<Self> {
+        if self.is_wildcard() {
+            Err(CometdError::InvalidChannel(
+                self.0,
+                "can't publish to wildcard channel",
+            ))
+        } else if self.is_meta() {
+            Err(CometdError::InvalidChannel(
+                self.0,
+                "can't publish to meta channel",
+            ))
+        } else {
+            Ok(self)
+        }
+    }
+}
+
+/// Validate channel name, it's `const`, so derive macro checks channel patterns in compile time.
+pub const fn validate_channel(channel: &str) -> Result<(), &'static str> {
+    let mut rest = match *channel.as_bytes() {
+        [b'/', ref rest @ ..] => rest,
+        _ => return Err("channel must start with `/`"),
+    };
+    // length of current segment and whether it consists of `*` only
+    let mut len = 0_usize;
+    let mut stars = true;
+
+    loop {
+        let last = match *rest {
+            [] => true,
+            [b'/', ref tail @ ..] => {
+                rest = tail;
+                false
+            }
+            [byte, ref tail @ ..] => {
+                if byte.is_ascii_whitespace() || byte.is_ascii_control() {
+                    return Err("channel segment contains whitespace or control character");
+                }
+                len += 1;
+                stars &= byte == b'*';
+                rest = tail;
+                continue;
+            }
+        };
+
+        if len == 0 {
+            return Err("channel segments can't be empty");
+        }
+        if stars && len <= 2 && !last {
+            return Err("wildcard can be only last segment");
+        }
+        if last {
+            return Ok(());
+        }
+        len = 0;
+        stars = true;
+    }
+}
+
+impl Display for ChannelId {
+    #[inline(always)]
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+impl Deref for ChannelId {
+    type Target = str;
+
+    #[inline(always)]
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl AsRef<str> for ChannelId {
+    #[inline(always)]
+    fn as_ref(&self) -> &str {
+        &self.0
+    }
+}
+
+impl FromStr for ChannelId {
+    type Err = CometdError;
+
+    #[inline(always)]
+    fn from_str(channel: &str) -> CometdResult<Self> {
+        Self::new(channel)
+    }
+}
+
+impl TryFrom<String> for ChannelId {
+    type Error = CometdError;
+
+    #[inline(always)]
+    fn try_from(channel: String) -> CometdResult<Self> {
+        Self::new(channel)
+    }
+}
+
+impl From<ChannelId> for String {
+    #[inline(always)]
+    fn from(channel: ChannelId) -> Self {
+        channel.0.into()
+    }
+}
+
+impl From<ChannelId> for ChannelPattern {
+    #[inline(always)]
+    fn from(channel: ChannelId) -> Self {
+        Self::new(channel.0)
+    }
+}
+
+impl PartialEq<str> for ChannelId {
+    #[inline(always)]
+    fn eq(&self, other: &str) -> bool {
+        *self.0 == *other
+    }
+}
+
+impl PartialEq<&str> for ChannelId {
+    #[inline(always)]
+    fn eq(&self, other: &&str) -> bool {
+        *self.0 == **other
+    }
+}
+
+impl Serialize for ChannelId {
+    #[inline(always)]
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&self.0)
+    }
+}
+
+impl<'de> Deserialize<'de> for ChannelId {
+    #[inline]
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        Self::new(String::deserialize(deserializer)?).map_err(de::Error::custom)
+    }
+}
@@ -0,0 +1,79 @@
+use core::fmt::{self, Display, Formatter};
+
+/// Channel name pattern with Bayeux wildcards:
+/// `*` matches exactly one segment, `**` matches one or more segments.
+///
+/// # Example
+/// ```rust
+/// # use cometd_client::types::ChannelPattern;
+/// let pattern = ChannelPattern::new("/orders/**");
+///
+/// assert!(pattern.matches("/orders/1"));
+/// assert!(pattern.matches("/orders/1/items"));
+/// assert!(!pattern.matches("/orders"));
+/// assert!(ChannelPattern::new("/prices/*").matches("/prices/btc"));
+/// assert!(!ChannelPattern::new("/prices/*").matches("/prices/btc/usd"));
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct ChannelPattern(Box<str>);
+
+impl ChannelPattern {
+    /// Create pattern from string, string without wildcards matches only same channel.
+    #[inline]
+    pub fn new(pattern: impl Into<Box<str>>) -> Self {
+        Self(pattern.into())
+    }
+
+    /// Return pattern as string.
+    #[inline(always)]
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+
+    /// Check if `channel` matches pattern.
+    #[inline]
+    pub fn matches(&self, channel: &str) -> bool {
+        let pattern = self.0.split('/').collect::<Vec<_>>();
+        let channel = channel.split('/').collect::<Vec<_>>();
+
+        matches_segments(&pattern, &channel)
+    }
+}
+
+fn matches_segments(pattern: &[&str], channel: &[&str]) -> bool {
+    match (pattern.split_first(), channel.split_first()) {
+        (None, None) => true,
+        (Some((&"**", rest)), Some(_)) => {
+            // `**` takes one segment at least
+            (1..=channel.len())
+                .filter_map(|taken| channel.get(taken..))
+                .any(|channel_rest| matches_segments(rest, channel_rest))
+        }
+        (Some((&"*", rest)), Some((_, channel_rest))) => matches_segments(rest, channel_rest),
+        (Some((segment, rest)), Some((channel_segment, channel_rest))) => {
+            segment == channel_segment && matches_segments(rest, channel_rest)
+        }
+        _ => false,
+    }
+}
+
+impl Display for ChannelPattern {
+    #[inline(always)]
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+impl From<&str> for ChannelPattern {
+    #[inline(always)]
+    fn from(pattern: &str) -> Self {
+        Self::new(pattern)
+    }
+}
+
+impl From<String> for ChannelPattern {
+    #[inline(always)]
+    fn from(pattern: String) -> Self {
+        Self::new(pattern)
+    }
+}
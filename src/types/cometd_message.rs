@@ -0,0 +1,67 @@
+use crate::types::{ChannelId, CometdError, CometdResult, ErrorKind};
+use serde::de::DeserializeOwned;
+use serde_json::Value as JsonValue;
+
+/// Message type which is parsed from `data` of received message.
+///
+/// It's implemented for every [`DeserializeOwned`] type,
+/// enum which payload depends on channel can implement it through `#[derive(CometdMessage)]`
+/// (`derive` feature):
+///
+/// ```rust
+/// # #[cfg(feature = "derive")] {
+/// use cometd_client::types::CometdMessage;
+/// # #[derive(Debug, serde::Deserialize)] struct Order;
+/// # #[derive(Debug, serde::Deserialize)] struct Price;
+///
+/// #[derive(Debug, CometdMessage)]
+/// enum Msg {
+///     #[cometd(channel = "/orders/**")]
+///     Order(Order),
+///     #[cometd(channel = "/prices/*")]
+///     Price(Price),
+///     #[cometd(channel = "/heartbeat")]
+///     Heartbeat,
+/// }
+/// # }
+/// ```
+///
+/// Variant is picked by first matching channel pattern,
+/// [`CometdError::UnmatchedChannel`] is returned if there is no one.
+/// Messages without `data` are parsed into unit variants, newtype variants are left empty.
+pub trait CometdMessage: Sized {
+    /// Parse message `data` which was received from `channel`.
+    fn from_data(channel: Option<&ChannelId>, data: JsonValue) -> CometdResult<Self>;
+
+    /// Build message which was received from `channel` without `data`,
+    /// `None` means there is no message (default).
+    #[inline(always)]
+    fn from_empty(_channel: Option<&ChannelId>) -> CometdResult<Option<Self>> {
+        Ok(None)
+    }
+}
+
+impl<T: DeserializeOwned> CometdMessage for T {
+    #[inline(always)]
+    fn from_data(_channel: Option<&ChannelId>, data: JsonValue) -> CometdResult<Self> {
+        parse_data(data)
+    }
+}
+
+/// Parse `data` as `T`, it's used by derive macro.
+#[inline(always)]
+pub fn parse_data<T: DeserializeOwned>(data: JsonValue) -> CometdResult<T> {
+    serde_json::from_value(data).map_err(|error| CometdError::ParseBody(ErrorKind::Connect, error))
+}
+
+/// Parse message `data` (if any) which was received from `channel`.
+#[inline]
+pub(crate) fn parse_message<T: CometdMessage>(
+    channel: Option<&ChannelId>,
+    data: Option<JsonValue>,
+) -> CometdResult<Option<T>> {
+    data.map_or_else(
+        || T::from_empty(channel),
+        |data| T::from_data(channel, data).map(Some),
+    )
+}
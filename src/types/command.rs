@@ -1,6 +1,43 @@
+use crate::types::{ChannelId, CometdError, CometdResult};
 use serde_json::Value as JsonValue;
+use tokio::sync::oneshot;
+
+pub(crate) type ReplySender<T> = oneshot::Sender<CometdResult<T>>;
 
 #[derive(Debug)]
 pub(crate) enum Command {
-    Subscribe(JsonValue),
+    Subscribe {
+        subscriptions: Box<[ChannelId]>,
+        reply_tx: ReplySender<Vec<(ChannelId, CometdResult<()>)>>,
+    },
+    Unsubscribe {
+        subscriptions: Box<[ChannelId]>,
+        reply_tx: ReplySender<Vec<(ChannelId, CometdResult<()>)>>,
+    },
+    /// Take subscription handle, subscribe if client isn't subscribed yet.
+    /// Handle is taken even if subscription fails, sender releases it.
+    Acquire {
+        channel: ChannelId,
+        reply_tx: ReplySender<()>,
+    },
+    Publish {
+        channel: ChannelId,
+        data: JsonValue,
+        reply_tx: ReplySender<()>,
+    },
+    Call {
+        id: String,
+        channel: ChannelId,
+        data: JsonValue,
+        reply_tx: ReplySender<Option<JsonValue>>,
+    },
+}
+
+/// Notifications which are sent to client task without reply.
+#[derive(Debug)]
+pub(crate) enum Notification {
+    /// Subscription handle to channel was dropped.
+    Released(ChannelId),
+    /// Error which should be sent to event channel.
+    Error(CometdError),
 }
@@ -0,0 +1,38 @@
+use serde::{Deserialize, Serialize};
+
+/// Bayeux transport which is used after handshake.
+///
+/// Handshake is always done with HTTP request (with first of
+/// `long-polling` or `callback-polling` from preferred connection types),
+/// other requests go through chosen transport.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[non_exhaustive]
+pub enum ConnectionType {
+    /// Every request is separate HTTP POST request, server holds connect request until
+    /// there are new messages.
+    #[default]
+    #[serde(rename = "long-polling")]
+    LongPolling,
+    /// Every request is separate HTTP GET request with messages in `message` query parameter,
+    /// server wraps reply in JSONP callback.
+    /// It's useful if POST requests are blocked by proxy.
+    #[serde(rename = "callback-polling")]
+    CallbackPolling,
+    /// All requests and messages go through one websocket.
+    #[cfg(feature = "websocket")]
+    #[serde(rename = "websocket")]
+    WebSocket,
+}
+
+impl ConnectionType {
+    /// Return name of connection type which is used in Bayeux messages.
+    #[inline]
+    pub const fn as_str(self) -> &'static str {
+        match self {
+            Self::LongPolling => "long-polling",
+            Self::CallbackPolling => "callback-polling",
+            #[cfg(feature = "websocket")]
+            Self::WebSocket => "websocket",
+        }
+    }
+}
@@ -1,7 +1,7 @@
 // https://github.com/rust-lang/rust-clippy/issues/10198
 #![allow(clippy::std_instead_of_core)]
 
-use crate::types::Reconnect;
+use crate::types::{ChannelId, ChannelPattern, Reconnect};
 use hyper::{http::uri::InvalidUri, Error as HyperError, StatusCode};
 use serde_json::Error as JsonError;
 use std::{borrow::Cow, error::Error};
@@ -15,19 +15,29 @@ pub type CometdResult<T> = Result<T, CometdError>;
 pub enum ErrorKind {
     Handshake,
     Subscribe,
+    Unsubscribe,
     Connect,
     Disconnect,
+    Publish,
+    Call,
 }
 
 #[allow(missing_docs)]
 #[derive(Debug, thiserror::Error)]
+#[non_exhaustive]
 pub enum CometdError {
     #[error("Endpoint wasn't set in builder.")]
     MissingEndpoint,
+    #[error("Connection types weren't set in builder.")]
+    MissingConnectionType,
     #[error("Url parse error: `{0}`.")]
     InvalidUrl(#[from] UrlParseError),
     #[error("Url parse error: `{0}`.")]
     InvalidUri(#[from] InvalidUri),
+    #[error("Invalid channel `{0}`: {1}.")]
+    InvalidChannel(Box<str>, &'static str),
+    #[error("Channel `{0}` doesn't match any variant of `{1}`.")]
+    UnmatchedChannel(Box<str>, &'static str),
     #[error("Got request error at {0:?}: `{1}`.")]
     Request(ErrorKind, HyperError),
     #[error("Got request timeout at {0:?}.")]
@@ -40,10 +50,27 @@ pub enum CometdError {
     FetchBody(ErrorKind, HyperError),
     #[error("Got parsing body error at {0:?}: `{1}`.")]
     ParseBody(ErrorKind, JsonError),
+    #[error("Got serializing data error at {0:?}: `{1}`.")]
+    SerializeData(ErrorKind, JsonError),
     #[error("Got wrong response at {0:?}: `{2}`")]
     WrongResponse(ErrorKind, Reconnect, Cow<'static, str>),
     #[error("Make handshake before {0:?} request.")]
     MissingClientId(ErrorKind),
+    #[cfg(feature = "websocket")]
+    #[error("Got websocket error at {0:?}: `{1}`.")]
+    WebSocket(ErrorKind, Box<tokio_tungstenite::tungstenite::Error>),
+    #[error("Got resubscribe error for `{0}`: `{1}`.")]
+    Resubscribe(ChannelId, Box<CometdError>),
+    /// Subscription handle isn't read fast enough, its new messages are dropped
+    /// until it has free space again.
+    #[error("Subscription `{0}` is full, its messages are dropped.")]
+    SubscriptionOverflow(ChannelPattern),
+    #[error("Handler of `{0}` returned error: `{1}`.")]
+    Handler(ChannelPattern, Box<dyn Error + Sync + Send + 'static>),
+    #[error("Handler of `{0}` panicked: `{1}`.")]
+    HandlerPanic(ChannelPattern, String),
+    #[error("Client task has been stopped.")]
+    ClientClosed,
     #[error("Got unexpected error: `{0}`")]
     Unexpected(Box<dyn Error + Sync + Send + 'static>),
 }
@@ -57,6 +84,21 @@ impl CometdError {
         Self::WrongResponse(kind, advice, Cow::from(error_message))
     }
 
+    /// Return `true` if error was caused by network, HTTP or websocket layer,
+    /// not by cometd server response.
+    #[inline]
+    pub const fn is_transport(&self) -> bool {
+        match *self {
+            Self::Request(..)
+            | Self::RequestTimeout(..)
+            | Self::StatusCode(..)
+            | Self::FetchBody(..) => true,
+            #[cfg(feature = "websocket")]
+            Self::WebSocket(..) => true,
+            _ => false,
+        }
+    }
+
     #[inline(always)]
     pub(crate) fn unexpected<E: Error + Sync + Send + 'static>(error: E) -> Self {
         Self::Unexpected(Box::from(error))
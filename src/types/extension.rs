@@ -0,0 +1,62 @@
+mod ack;
+mod replay;
+mod timesync;
+
+pub use ack::*;
+pub use replay::*;
+pub use timesync::*;
+
+use crate::types::Message;
+use core::fmt::Debug;
+
+/// Trait which can be used for implementing Bayeux extension.
+///
+/// Extensions are called in order of registration for every message which client sends
+/// or receives, meta messages (`/meta/**`) and other messages have separate hooks.
+/// Extension can change message (usually `ext` field) or drop it by returning `false`.
+/// Request which was dropped fails as if server didn't reply to it.
+///
+/// # Example:
+/// ```rust,no_run
+/// # use cometd_client::types::{Extension, Message};
+///     #[derive(Debug)]
+///     struct Token(String);
+///
+///     impl Extension for Token {
+///         fn outgoing_meta(&self, message: &mut Message) -> bool {
+///             if message.channel.as_deref() == Some("/meta/handshake") {
+///                 message
+///                     .ext
+///                     .get_or_insert_with(Default::default)
+///                     .insert("token".to_owned(), self.0.clone().into());
+///             }
+///
+///             true
+///         }
+///     }
+/// ```
+pub trait Extension: Debug + Sync + Send + 'static {
+    /// Called for meta message before it's sent.
+    #[inline(always)]
+    fn outgoing_meta(&self, _message: &mut Message) -> bool {
+        true
+    }
+
+    /// Called for non-meta message before it's sent.
+    #[inline(always)]
+    fn outgoing(&self, _message: &mut Message) -> bool {
+        true
+    }
+
+    /// Called for meta message after it's received.
+    #[inline(always)]
+    fn incoming_meta(&self, _message: &mut Message) -> bool {
+        true
+    }
+
+    /// Called for non-meta message after it's received.
+    #[inline(always)]
+    fn incoming(&self, _message: &mut Message) -> bool {
+        true
+    }
+}
@@ -0,0 +1,91 @@
+use crate::types::{Extension, Message};
+use core::sync::atomic::{AtomicBool, AtomicI64, Ordering};
+use serde_json::Value as JsonValue;
+
+/// Client side of CometD acknowledgement extension.
+///
+/// It asks server to enable acknowledgements in handshake and sends id of last received batch
+/// with every connect request, so server redelivers messages which were lost
+/// (e.g. if connect request was timed out).
+///
+/// Usually it's enabled through [`crate::CometdClientBuilder::ack_extension`]
+/// and read through [`crate::CometdClient::ack`].
+///
+/// # Example
+/// ```rust,no_run
+/// # use cometd_client::{CometdClient, CometdClientBuilder};
+/// # let _ = || -> cometd_client::types::CometdResult<_> {
+/// let client = CometdClientBuilder::new(&"http://[::1]:1025/notifications/".parse()?)
+///     .ack_extension()
+///     .build()?;
+/// # let client: CometdClient<()> = client;
+/// # Ok(()) };
+/// ```
+#[derive(Debug, Default)]
+pub struct AckExtension {
+    server_supports_acks: AtomicBool,
+    batch: AtomicI64,
+}
+
+impl AckExtension {
+    /// Construct a new `AckExtension`.
+    #[inline(always)]
+    pub const fn new() -> Self {
+        Self {
+            server_supports_acks: AtomicBool::new(false),
+            batch: AtomicI64::new(0),
+        }
+    }
+
+    /// Return id of last received batch.
+    #[inline]
+    pub fn batch(&self) -> i64 {
+        self.batch.load(Ordering::Acquire)
+    }
+}
+
+impl Extension for AckExtension {
+    fn outgoing_meta(&self, message: &mut Message) -> bool {
+        match message.channel.as_deref() {
+            Some("/meta/handshake") => {
+                self.server_supports_acks.store(false, Ordering::Release);
+                self.batch.store(0, Ordering::Release);
+
+                insert_ack(message, JsonValue::Bool(true));
+            }
+            Some("/meta/connect") if self.server_supports_acks.load(Ordering::Acquire) => {
+                insert_ack(message, JsonValue::from(self.batch()));
+            }
+            _ => {}
+        }
+
+        true
+    }
+
+    fn incoming_meta(&self, message: &mut Message) -> bool {
+        let ack = message.ext.as_ref().and_then(|ext| ext.get("ack"));
+
+        match message.channel.as_deref() {
+            Some("/meta/handshake") => {
+                let enabled = ack.and_then(JsonValue::as_bool).unwrap_or_default();
+                self.server_supports_acks.store(enabled, Ordering::Release);
+            }
+            Some("/meta/connect") if message.successful == Some(true) => {
+                if let Some(batch) = ack.and_then(JsonValue::as_i64) {
+                    self.batch.store(batch, Ordering::Release);
+                }
+            }
+            _ => {}
+        }
+
+        true
+    }
+}
+
+#[inline(always)]
+fn insert_ack(message: &mut Message, ack: JsonValue) {
+    message
+        .ext
+        .get_or_insert_with(Default::default)
+        .insert("ack".to_owned(), ack);
+}
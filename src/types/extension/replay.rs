@@ -0,0 +1,113 @@
+mod file;
+mod memory;
+
+pub use file::*;
+pub use memory::*;
+
+use crate::types::{Extension, Message};
+use core::{
+    fmt::Debug,
+    sync::atomic::{AtomicBool, Ordering},
+};
+use serde_json::{json, Value as JsonValue};
+
+/// Trait which can be used for implementing custom storage of replay ids.
+pub trait ReplayStore: Debug + Sync + Send + 'static {
+    /// Return last replay id of `channel`.
+    fn load(&self, channel: &str) -> Option<i64>;
+    /// Remember last replay id of `channel`.
+    fn save(&self, channel: &str, replay_id: i64);
+}
+
+/// Client side of replay extension (e.g. Salesforce streaming API).
+///
+/// It remembers replay id (`data.event.replayId` or `data.replayId`) of every received message
+/// in [`ReplayStore`] and sends last replay id of channel with subscribe request,
+/// so server sends messages which were missed.
+///
+/// Replay ids are kept per concrete channel of received message and are looked up by
+/// subscription channel, so wildcard subscriptions (e.g. `/topic/*`) are sent without
+/// replay id and server uses its default (Salesforce doesn't support wildcards in replay either).
+/// Subscribe to concrete channels for replay.
+///
+/// # Example
+/// ```rust,no_run
+/// # use cometd_client::{CometdClient, CometdClientBuilder, types::extension::{FileReplayStore, ReplayExtension}};
+/// # let _ = || -> Result<_, Box<dyn std::error::Error>> {
+/// let store = FileReplayStore::new("replay_ids.json")?;
+///
+/// let client = CometdClientBuilder::new(&"http://[::1]:1025/notifications/".parse()?)
+///     .extension(ReplayExtension::new(store))
+///     .build()?;
+/// # let client: CometdClient<()> = client;
+/// # Ok(()) };
+/// ```
+#[derive(Debug)]
+pub struct ReplayExtension {
+    store: Box<dyn ReplayStore>,
+    server_supports_replay: AtomicBool,
+}
+
+impl ReplayExtension {
+    /// Construct a new `ReplayExtension` which stores replay ids in `store`.
+    #[inline(always)]
+    pub fn new(store: impl ReplayStore) -> Self {
+        Self {
+            store: Box::new(store),
+            server_supports_replay: AtomicBool::new(false),
+        }
+    }
+}
+
+impl Extension for ReplayExtension {
+    fn outgoing_meta(&self, message: &mut Message) -> bool {
+        let replay = match message.channel.as_deref() {
+            Some("/meta/handshake") => Some(JsonValue::Bool(true)),
+            Some("/meta/subscribe") if self.server_supports_replay.load(Ordering::Acquire) => {
+                message.subscription.as_deref().and_then(|subscription| {
+                    let replay_id = self.store.load(subscription)?;
+                    Some(json!({ subscription: replay_id }))
+                })
+            }
+            _ => None,
+        };
+
+        if let Some(replay) = replay {
+            message
+                .ext
+                .get_or_insert_with(Default::default)
+                .insert("replay".to_owned(), replay);
+        }
+
+        true
+    }
+
+    fn incoming_meta(&self, message: &mut Message) -> bool {
+        if message.channel.as_deref() == Some("/meta/handshake") {
+            let enabled = message
+                .ext
+                .as_ref()
+                .and_then(|ext| ext.get("replay")?.as_bool())
+                .unwrap_or_default();
+            self.server_supports_replay
+                .store(enabled, Ordering::Release);
+        }
+
+        true
+    }
+
+    fn incoming(&self, message: &mut Message) -> bool {
+        let replay_id = message.data.as_ref().and_then(|data| {
+            data.get("event")
+                .and_then(|event| event.get("replayId"))
+                .or_else(|| data.get("replayId"))?
+                .as_i64()
+        });
+
+        if let (Some(channel), Some(replay_id)) = (message.channel.as_deref(), replay_id) {
+            self.store.save(channel, replay_id);
+        }
+
+        true
+    }
+}
@@ -0,0 +1,188 @@
+use crate::types::extension::ReplayStore;
+use std::{
+    collections::BTreeMap,
+    fs::{self, File},
+    io::{self, Write as _},
+    path::{Path, PathBuf},
+    sync::{Arc, Mutex, MutexGuard, PoisonError},
+};
+
+/// Keeps replay ids in JSON file (`{ "<channel>": <replay id> }`),
+/// so process resumes from last received message after restart.
+///
+/// File is rewritten in background (blocking tokio thread) after received message with new
+/// replay id, replay ids which were received during write are batched into next one.
+/// File is synced to disk before and after it replaces previous one, so it's never
+/// left half-written. Pending replay ids are written when store is dropped
+/// or through [`FileReplayStore::flush`].
+///
+/// Background write errors are ignored, in that case file keeps previous replay ids.
+#[derive(Debug)]
+pub struct FileReplayStore {
+    shared: Arc<Shared>,
+}
+
+#[derive(Debug)]
+struct Shared {
+    path: PathBuf,
+    state: Mutex<State>,
+    // only one write at a time, so temporary file isn't shared
+    write_lock: Mutex<()>,
+}
+
+#[derive(Debug)]
+struct State {
+    replay_ids: BTreeMap<String, i64>,
+    /// Replay ids were changed after last write.
+    dirty: bool,
+    /// Background write is scheduled or running.
+    writing: bool,
+}
+
+impl FileReplayStore {
+    /// Construct a new `FileReplayStore` and load replay ids from `path` if file exists.
+    #[inline]
+    pub fn new(path: impl Into<PathBuf>) -> io::Result<Self> {
+        let path = path.into();
+        let replay_ids = match fs::read(&path) {
+            Ok(raw) => serde_json::from_slice(&raw)?,
+            Err(error) if error.kind() == io::ErrorKind::NotFound => BTreeMap::new(),
+            Err(error) => return Err(error),
+        };
+
+        Ok(Self {
+            shared: Arc::new(Shared {
+                path,
+                state: Mutex::new(State {
+                    replay_ids,
+                    dirty: false,
+                    writing: false,
+                }),
+                write_lock: Mutex::new(()),
+            }),
+        })
+    }
+
+    /// Write pending replay ids to file and wait until they are synced to disk.
+    #[inline]
+    pub fn flush(&self) -> io::Result<()> {
+        self.shared.flush()
+    }
+}
+
+impl Shared {
+    #[inline(always)]
+    fn state(&self) -> MutexGuard<'_, State> {
+        self.state.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    fn flush(&self) -> io::Result<()> {
+        let _write_lock = self
+            .write_lock
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner);
+        let replay_ids = {
+            let mut state = self.state();
+            if !state.dirty {
+                return Ok(());
+            }
+            state.dirty = false;
+            state.replay_ids.clone()
+        };
+
+        let result = write(&self.path, &replay_ids);
+        if result.is_err() {
+            self.state().dirty = true;
+        }
+
+        result
+    }
+
+    /// Write replay ids until there are no changes.
+    fn write_in_background(&self) {
+        loop {
+            {
+                let mut state = self.state();
+                if !state.dirty {
+                    state.writing = false;
+                    return;
+                }
+            }
+
+            if self.flush().is_err() {
+                // keep changes for next save or drop
+                self.state().writing = false;
+                return;
+            }
+        }
+    }
+}
+
+impl ReplayStore for FileReplayStore {
+    #[inline]
+    fn load(&self, channel: &str) -> Option<i64> {
+        self.shared.state().replay_ids.get(channel).copied()
+    }
+
+    #[inline]
+    fn save(&self, channel: &str, replay_id: i64) {
+        let mut state = self.shared.state();
+        if state.replay_ids.insert(channel.to_owned(), replay_id) == Some(replay_id) {
+            return;
+        }
+
+        state.dirty = true;
+        if state.writing {
+            return;
+        }
+        state.writing = true;
+        drop(state);
+
+        let shared = Arc::clone(&self.shared);
+        match tokio::runtime::Handle::try_current() {
+            Ok(runtime) => {
+                runtime.spawn_blocking(move || shared.write_in_background());
+            }
+            Err(_) => shared.write_in_background(),
+        }
+    }
+}
+
+impl Drop for FileReplayStore {
+    #[inline]
+    fn drop(&mut self) {
+        let _ = self.shared.flush();
+    }
+}
+
+/// Write replay ids into synced temporary file and then replace store file with it.
+fn write(path: &Path, replay_ids: &BTreeMap<String, i64>) -> io::Result<()> {
+    let mut tmp_path = path.to_owned().into_os_string();
+    tmp_path.push(".tmp");
+
+    let mut file = File::create(&tmp_path)?;
+    file.write_all(&serde_json::to_vec(replay_ids)?)?;
+    file.sync_all()?;
+    drop(file);
+
+    fs::rename(&tmp_path, path)?;
+    sync_parent_dir(path)
+}
+
+/// Sync directory entry of renamed file.
+#[cfg(unix)]
+fn sync_parent_dir(path: &Path) -> io::Result<()> {
+    let dir = path
+        .parent()
+        .filter(|dir| !dir.as_os_str().is_empty())
+        .unwrap_or_else(|| Path::new("."));
+
+    File::open(dir)?.sync_all()
+}
+
+/// Directories can't be opened on other platforms, rename is durable there.
+#[cfg(not(unix))]
+#[inline(always)]
+const fn sync_parent_dir(_path: &Path) -> io::Result<()> {
+    Ok(())
+}
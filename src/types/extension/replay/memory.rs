@@ -0,0 +1,38 @@
+use crate::types::extension::ReplayStore;
+use std::{
+    collections::HashMap,
+    sync::{Mutex, PoisonError},
+};
+
+/// Keeps replay ids in memory, so they survive reconnects but not process restart.
+#[derive(Debug, Default)]
+pub struct MemoryReplayStore {
+    replay_ids: Mutex<HashMap<String, i64>>,
+}
+
+impl MemoryReplayStore {
+    /// Construct a new empty `MemoryReplayStore`.
+    #[inline(always)]
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+impl ReplayStore for MemoryReplayStore {
+    #[inline]
+    fn load(&self, channel: &str) -> Option<i64> {
+        self.replay_ids
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .get(channel)
+            .copied()
+    }
+
+    #[inline]
+    fn save(&self, channel: &str, replay_id: i64) {
+        self.replay_ids
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .insert(channel.to_owned(), replay_id);
+    }
+}
@@ -0,0 +1,155 @@
+use crate::types::{Extension, Message};
+use core::time::Duration;
+use serde_json::json;
+use std::{
+    collections::VecDeque,
+    sync::{Mutex, MutexGuard, PoisonError},
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+const MAX_SAMPLES: usize = 10;
+
+/// Client side of CometD timesync extension.
+///
+/// It estimates offset between server and client clocks and network lag
+/// by timestamps which are sent with every meta message.
+/// Estimations are averaged over last 10 meta replies.
+///
+/// Usually it's enabled through [`crate::CometdClientBuilder::timesync_extension`]
+/// and read through [`crate::CometdClient::timesync`].
+#[derive(Debug, Default)]
+pub struct TimesyncExtension {
+    state: Mutex<State>,
+}
+
+#[derive(Debug, Default)]
+struct State {
+    lags: VecDeque<i64>,
+    offsets: VecDeque<i64>,
+    lag: i64,
+    offset: i64,
+}
+
+impl TimesyncExtension {
+    /// Construct a new `TimesyncExtension`.
+    #[inline(always)]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Return estimated server clock offset in milliseconds (server time minus client time).
+    /// It's zero until first meta reply.
+    #[inline]
+    pub fn offset(&self) -> i64 {
+        self.state().offset
+    }
+
+    /// Return estimated one-way network lag (half of round trip).
+    #[inline]
+    pub fn lag(&self) -> Duration {
+        Duration::from_millis(self.state().lag.try_into().unwrap_or_default())
+    }
+
+    /// Return estimated round trip time.
+    #[inline]
+    pub fn round_trip(&self) -> Duration {
+        self.lag().saturating_mul(2)
+    }
+
+    /// Return estimated current time on server.
+    #[inline]
+    pub fn server_time(&self) -> SystemTime {
+        shift(SystemTime::now(), self.offset())
+    }
+
+    /// Convert server timestamp (milliseconds since unix epoch) into local time.
+    ///
+    /// # Example
+    /// ```rust
+    /// # use cometd_client::types::extension::TimesyncExtension;
+    /// # use std::time::{Duration, UNIX_EPOCH};
+    /// let timesync = TimesyncExtension::new();
+    ///
+    /// assert_eq!(timesync.to_local_time(1_000), UNIX_EPOCH + Duration::from_secs(1));
+    /// ```
+    #[inline]
+    pub fn to_local_time(&self, server_timestamp: u64) -> SystemTime {
+        shift(
+            UNIX_EPOCH + Duration::from_millis(server_timestamp),
+            -self.offset(),
+        )
+    }
+
+    #[inline(always)]
+    fn state(&self) -> MutexGuard<'_, State> {
+        self.state.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+}
+
+impl Extension for TimesyncExtension {
+    fn outgoing_meta(&self, message: &mut Message) -> bool {
+        let (lag, offset) = {
+            let state = self.state();
+            (state.lag, state.offset)
+        };
+
+        message.ext.get_or_insert_with(Default::default).insert(
+            "timesync".to_owned(),
+            json!({ "tc": now_millis(), "l": lag, "o": offset }),
+        );
+
+        true
+    }
+
+    fn incoming_meta(&self, message: &mut Message) -> bool {
+        let timesync = message.ext.as_ref().and_then(|ext| ext.get("timesync"));
+        let field = |name| timesync.and_then(|timesync| timesync.get(name)?.as_i64());
+
+        if let (Some(tc), Some(ts), Some(p)) = (field("tc"), field("ts"), field("p")) {
+            let lag = (now_millis() - tc - p) / 2;
+            let offset = ts - tc - lag;
+
+            let mut state = self.state();
+            state.lags.push_back(lag);
+            state.offsets.push_back(offset);
+            if state.offsets.len() > MAX_SAMPLES {
+                state.lags.pop_front();
+                state.offsets.pop_front();
+            }
+
+            state.lag = average(&state.lags);
+            state.offset = average(&state.offsets);
+        }
+
+        true
+    }
+}
+
+#[inline(always)]
+fn now_millis() -> i64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|duration| duration.as_millis())
+        .unwrap_or_default()
+        .try_into()
+        .unwrap_or(i64::MAX)
+}
+
+#[inline(always)]
+fn average(samples: &VecDeque<i64>) -> i64 {
+    let len = i64::try_from(samples.len()).unwrap_or(i64::MAX).max(1);
+
+    samples.iter().sum::<i64>() / len
+}
+
+#[inline(always)]
+fn shift(time: SystemTime, millis: i64) -> SystemTime {
+    let delta = Duration::from_millis(millis.unsigned_abs());
+
+    if millis >= 0 {
+        time.checked_add(delta)
+    } else {
+        time.checked_sub(delta)
+    }
+    .unwrap_or(time)
+}
@@ -1,40 +1,82 @@
+use crate::types::ChannelId;
 use serde::{Deserialize, Serialize};
-use serde_json::Value as JsonValue;
+use serde_json::{Map as JsonMap, Value as JsonValue};
 use serde_with::skip_serializing_none;
 
 /// Contains channel name of message received from cometd server.
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct Data<Msg> {
     /// Channel name from which was received message.
-    pub channel: Option<String>,
+    pub channel: Option<ChannelId>,
     /// Received message.
     pub message: Option<Msg>,
 }
 
+/// Bayeux message, it's used by [`crate::types::Extension`].
 #[skip_serializing_none]
-#[derive(Debug, Default, Deserialize, Serialize)]
-pub(crate) struct Message {
-    pub(crate) id: Option<String>,
-    pub(crate) version: Option<String>,
+#[derive(Debug, Default, Clone, Deserialize, Serialize)]
+pub struct Message {
+    /// Message id, reply has same id as request.
+    pub id: Option<String>,
+    /// Bayeux protocol version (handshake only).
+    pub version: Option<String>,
+    /// Minimum supported Bayeux protocol version (handshake only).
     #[serde(rename = "minimumVersion")]
-    pub(crate) minimum_version: Option<String>,
-    pub(crate) channel: Option<String>,
+    pub minimum_version: Option<String>,
+    /// Channel name.
+    pub channel: Option<String>,
+    /// Client id which was given by server in handshake reply.
     #[serde(rename = "clientId")]
-    pub(crate) client_id: Option<Box<str>>,
+    pub client_id: Option<Box<str>>,
+    /// Connection types which are supported by client or server (handshake only).
     #[serde(rename = "supportedConnectionTypes")]
-    pub(crate) supported_connection_types: Option<Vec<String>>,
-    pub(crate) data: Option<JsonValue>,
-    pub(crate) successful: Option<bool>,
-    pub(crate) error: Option<String>,
-    pub(crate) advice: Option<Advice>,
+    pub supported_connection_types: Option<Vec<String>>,
+    /// Connection type which is used (connect only).
+    #[serde(rename = "connectionType")]
+    pub connection_type: Option<String>,
+    /// Channel to subscribe to or to unsubscribe from.
+    pub subscription: Option<String>,
+    /// Message payload.
+    pub data: Option<JsonValue>,
+    /// Whether request was successful (replies only).
+    pub successful: Option<bool>,
+    /// Error description (failed replies only).
+    pub error: Option<String>,
+    /// Server or client advice.
+    pub advice: Option<Advice>,
+    /// Extensions data.
+    pub ext: Option<JsonMap<String, JsonValue>>,
 }
 
+impl Message {
+    /// Return `true` if message belongs to `/meta/**` channel.
+    #[inline]
+    pub fn is_meta(&self) -> bool {
+        self.channel
+            .as_deref()
+            .is_some_and(|channel| channel.starts_with("/meta/"))
+    }
+
+    /// Return `true` if message is server reply to request with `ids`,
+    /// otherwise it's message which was pushed by server.
+    #[inline]
+    pub(crate) fn is_reply_to(&self, ids: &[String]) -> bool {
+        self.successful.is_some() && self.id.as_ref().is_some_and(|id| ids.contains(id))
+    }
+}
+
+/// Advice how client should behave.
 #[skip_serializing_none]
-#[derive(Debug, Default, Serialize, Deserialize)]
-pub(crate) struct Advice {
-    reconnect: Option<Reconnect>,
-    //interval: Option<u64>,
-    //timeout: Option<u64>,
+#[derive(Debug, Default, Clone, Serialize, Deserialize)]
+pub struct Advice {
+    /// What to do after connect or on error.
+    pub reconnect: Option<Reconnect>,
+    /// Milliseconds which client should wait before next connect request.
+    pub interval: Option<u64>,
+    /// Milliseconds which server holds connect request.
+    pub timeout: Option<u64>,
+    /// Alternative servers.
+    pub hosts: Option<Vec<String>>,
 }
 
 impl Advice {
@@ -42,11 +84,23 @@ impl Advice {
     pub(crate) fn reconnect(this: Option<Self>) -> Reconnect {
         this.and_then(|advice| advice.reconnect).unwrap_or_default()
     }
+
+    /// Overwrite fields which are present in `other`, `reconnect` is always taken from `other`,
+    /// because it's advice for reply which it came with.
+    #[inline]
+    pub(crate) fn update(&mut self, other: &Self) {
+        self.reconnect = other.reconnect;
+        self.interval = other.interval.or(self.interval);
+        self.timeout = other.timeout.or(self.timeout);
+        if other.hosts.is_some() {
+            self.hosts = other.hosts.clone();
+        }
+    }
 }
 
 /// Advice what to do on error.
 #[allow(missing_docs)]
-#[derive(Debug, Default, Clone, Copy, Serialize, Deserialize)]
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 #[serde(rename_all = "lowercase")]
 pub enum Reconnect {
     #[default]
@@ -0,0 +1,47 @@
+mod advice;
+mod no_retry;
+mod transport;
+
+pub use advice::*;
+pub use no_retry::*;
+pub use transport::*;
+
+use crate::types::{CometdError, ErrorKind};
+use core::{fmt::Debug, time::Duration};
+
+/// What to do with failed request.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RetryDecision {
+    /// Redo request after delay.
+    Retry(Duration),
+    /// Redo handshake after delay and then redo request.
+    Handshake(Duration),
+    /// Stop retrying and return error.
+    GiveUp,
+}
+
+/// Trait which can be used for implementing custom retry policy.
+///
+/// # Example:
+/// ```rust,no_run
+/// # use cometd_client::types::{retry_policy::RetryDecision, CometdError, ErrorKind, RetryPolicy};
+/// # use core::time::Duration;
+///     #[derive(Debug)]
+///     struct RetryTimeouts;
+///
+///     impl RetryPolicy for RetryTimeouts {
+///         fn decide(&self, error: &CometdError, _kind: ErrorKind, attempt: usize) -> RetryDecision {
+///             match *error {
+///                 CometdError::RequestTimeout(_) if attempt < 5 => {
+///                     RetryDecision::Retry(Duration::from_secs(1))
+///                 }
+///                 _ => RetryDecision::GiveUp,
+///             }
+///         }
+///     }
+/// ```
+pub trait RetryPolicy: Debug + Sync + Send + 'static {
+    /// Return decision for `error` which occurs at `kind` request.
+    /// `attempt` is number of previous retries of this request.
+    fn decide(&self, error: &CometdError, kind: ErrorKind, attempt: usize) -> RetryDecision;
+}
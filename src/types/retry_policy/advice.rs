@@ -0,0 +1,56 @@
+use crate::types::{
+    retry_policy::RetryDecision, Backoff, CometdError, ErrorKind, Reconnect, RetryPolicy,
+};
+
+/// `AdviceRetry` follows server `reconnect` advice.
+/// It redoes handshake on [`Reconnect::Handshake`] advice (or if handshake wasn't done),
+/// redoes request on [`Reconnect::Retry`] advice and gives up on any other error.
+///
+/// This is default policy of `CometdClient`.
+///
+/// # Example
+/// ```rust,no_run
+/// # use cometd_client::{types::{retry_policy::AdviceRetry, Backoff}, CometdClientBuilder};
+///
+/// # async {
+///     let client = CometdClientBuilder::new(&"http://[::1]:1025/".parse()?)
+///         .retry_policy(AdviceRetry::new(5, Backoff::default()))
+///         .build::<()>()?;
+/// # Result::<_, Box<dyn std::error::Error>>::Ok(())
+/// # };
+/// ```
+#[derive(Debug, Clone, Copy)]
+pub struct AdviceRetry {
+    number_of_retries: usize,
+    backoff: Backoff,
+}
+
+impl AdviceRetry {
+    /// Create `AdviceRetry` policy.
+    #[inline(always)]
+    pub const fn new(number_of_retries: usize, backoff: Backoff) -> Self {
+        Self {
+            number_of_retries,
+            backoff,
+        }
+    }
+}
+
+impl RetryPolicy for AdviceRetry {
+    fn decide(&self, error: &CometdError, _kind: ErrorKind, attempt: usize) -> RetryDecision {
+        if attempt >= self.number_of_retries {
+            return RetryDecision::GiveUp;
+        }
+
+        match *error {
+            CometdError::WrongResponse(_, Reconnect::Handshake, _)
+            | CometdError::MissingClientId(_) => {
+                RetryDecision::Handshake(self.backoff.delay(attempt))
+            }
+            CometdError::WrongResponse(_, Reconnect::Retry, _) => {
+                RetryDecision::Retry(self.backoff.delay(attempt))
+            }
+            _ => RetryDecision::GiveUp,
+        }
+    }
+}
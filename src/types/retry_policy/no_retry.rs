@@ -0,0 +1,12 @@
+use crate::types::{retry_policy::RetryDecision, CometdError, ErrorKind, RetryPolicy};
+
+/// `NoRetry` never redoes requests.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct NoRetry;
+
+impl RetryPolicy for NoRetry {
+    #[inline(always)]
+    fn decide(&self, _error: &CometdError, _kind: ErrorKind, _attempt: usize) -> RetryDecision {
+        RetryDecision::GiveUp
+    }
+}
@@ -0,0 +1,59 @@
+use crate::types::{
+    retry_policy::{AdviceRetry, RetryDecision},
+    Backoff, CometdError, ErrorKind, RetryPolicy,
+};
+use hyper::StatusCode;
+
+/// `TransportRetry` works like [`AdviceRetry`], but also redoes request
+/// on network errors, timeouts, `429 Too Many Requests` and `5xx` status codes.
+///
+/// # Example
+/// ```rust,no_run
+/// # use cometd_client::{types::{retry_policy::TransportRetry, Backoff}, CometdClientBuilder};
+///
+/// # async {
+///     let client = CometdClientBuilder::new(&"http://[::1]:1025/".parse()?)
+///         .retry_policy(TransportRetry::new(5, Backoff::default()))
+///         .build::<()>()?;
+/// # Result::<_, Box<dyn std::error::Error>>::Ok(())
+/// # };
+/// ```
+#[derive(Debug, Clone, Copy)]
+pub struct TransportRetry {
+    number_of_retries: usize,
+    backoff: Backoff,
+}
+
+impl TransportRetry {
+    /// Create `TransportRetry` policy.
+    #[inline(always)]
+    pub const fn new(number_of_retries: usize, backoff: Backoff) -> Self {
+        Self {
+            number_of_retries,
+            backoff,
+        }
+    }
+}
+
+impl RetryPolicy for TransportRetry {
+    fn decide(&self, error: &CometdError, kind: ErrorKind, attempt: usize) -> RetryDecision {
+        if attempt >= self.number_of_retries {
+            return RetryDecision::GiveUp;
+        }
+
+        match *error {
+            CometdError::Request(..)
+            | CometdError::RequestTimeout(..)
+            | CometdError::FetchBody(..) => RetryDecision::Retry(self.backoff.delay(attempt)),
+            CometdError::StatusCode(_, status_code, _)
+                if status_code.is_server_error()
+                    || status_code == StatusCode::TOO_MANY_REQUESTS =>
+            {
+                RetryDecision::Retry(self.backoff.delay(attempt))
+            }
+            _ => {
+                AdviceRetry::new(self.number_of_retries, self.backoff).decide(error, kind, attempt)
+            }
+        }
+    }
+}
@@ -0,0 +1,98 @@
+mod extract;
+mod handler;
+
+pub use {extract::*, handler::*};
+
+use crate::{
+    types::{ChannelPattern, CometdError, Notification, Subscription},
+    CometdClientInner,
+};
+use core::any::Any;
+use serde_json::Value as JsonValue;
+use std::sync::Arc;
+use tokio::{sync::Semaphore, task::JoinHandle};
+
+/// Handle of route which was added through [`crate::CometdClient::route`].
+///
+/// Route is stopped and its subscription is released when handle is dropped.
+#[derive(Debug)]
+#[must_use = "route is stopped when handle is dropped"]
+pub struct RouteHandle {
+    pattern: ChannelPattern,
+    task: JoinHandle<()>,
+}
+
+impl RouteHandle {
+    /// Spawn task which calls `handler` for every message from `subscription`,
+    /// at most `concurrency` handlers are running at the same time.
+    pub(crate) fn spawn<Args>(
+        inner: Arc<CometdClientInner>,
+        mut subscription: Subscription<JsonValue>,
+        concurrency: usize,
+        handler: impl Handler<Args>,
+    ) -> Self {
+        let pattern = ChannelPattern::from(subscription.channel().clone());
+        let route_pattern = pattern.clone();
+        let handler = Arc::new(handler);
+        let semaphore = Arc::new(Semaphore::new(concurrency.max(1)));
+
+        let task = tokio::spawn(async move {
+            while let Some(data) = subscription.recv().await {
+                let data = match data {
+                    Ok(data) => data,
+                    Err(error) => {
+                        inner.notify(Notification::Error(error));
+                        continue;
+                    }
+                };
+                let Ok(permit) = Arc::clone(&semaphore).acquire_owned().await else {
+                    break;
+                };
+
+                let handler = Arc::clone(&handler);
+                let inner = Arc::clone(&inner);
+                let pattern = route_pattern.clone();
+                tokio::spawn(async move {
+                    // handler runs in separate task, so its panic is caught by join handle
+                    let result = tokio::spawn(async move { handler.call(data).await }).await;
+                    drop(permit);
+
+                    let error = match result {
+                        Ok(Ok(())) => return,
+                        Ok(Err(error)) => CometdError::Handler(pattern, error),
+                        Err(error) if error.is_panic() => {
+                            CometdError::HandlerPanic(pattern, panic_message(error.into_panic()))
+                        }
+                        Err(_) => return,
+                    };
+                    inner.notify(Notification::Error(error));
+                });
+            }
+        });
+
+        Self { pattern, task }
+    }
+
+    /// Return pattern of routed channels.
+    #[inline(always)]
+    pub const fn pattern(&self) -> &ChannelPattern {
+        &self.pattern
+    }
+}
+
+impl Drop for RouteHandle {
+    #[inline(always)]
+    fn drop(&mut self) {
+        self.task.abort();
+    }
+}
+
+fn panic_message(payload: Box<dyn Any + Send>) -> String {
+    match payload.downcast::<String>() {
+        Ok(message) => *message,
+        Err(payload) => payload.downcast_ref::<&str>().map_or_else(
+            || "unknown panic".to_owned(),
+            |message| (*message).to_owned(),
+        ),
+    }
+}
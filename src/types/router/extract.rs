@@ -0,0 +1,45 @@
+use crate::types::{ChannelId, CometdError, CometdResult, Data, ErrorKind};
+use serde::de::DeserializeOwned;
+use serde_json::Value as JsonValue;
+
+/// Extractor of handler argument from received message.
+///
+/// # Example
+/// ```rust
+/// # use cometd_client::types::{router::FromData, CometdResult, Data};
+/// # use serde_json::Value as JsonValue;
+/// struct Depth(usize);
+///
+/// impl FromData for Depth {
+///     fn from_data(data: &Data<JsonValue>) -> CometdResult<Self> {
+///         Ok(Depth(data.channel.as_ref().map_or(0, |channel| channel.segments().count())))
+///     }
+/// }
+/// ```
+pub trait FromData: Sized {
+    /// Extract value from `data`, error is reported and handler isn't called.
+    fn from_data(data: &Data<JsonValue>) -> CometdResult<Self>;
+}
+
+/// Extractor which parses message payload as `T`.
+#[derive(Debug, Clone, Default)]
+pub struct Json<T>(pub T);
+
+impl<T: DeserializeOwned> FromData for Json<T> {
+    #[inline]
+    fn from_data(data: &Data<JsonValue>) -> CometdResult<Self> {
+        serde_json::from_value(data.message.clone().unwrap_or_default())
+            .map(Json)
+            .map_err(|error| CometdError::ParseBody(ErrorKind::Connect, error))
+    }
+}
+
+impl FromData for ChannelId {
+    #[inline]
+    fn from_data(data: &Data<JsonValue>) -> CometdResult<Self> {
+        data.channel.clone().ok_or(CometdError::InvalidChannel(
+            Box::from(""),
+            "message has no channel",
+        ))
+    }
+}
@@ -0,0 +1,76 @@
+// https://github.com/rust-lang/rust-clippy/issues/10198
+#![allow(clippy::std_instead_of_core)]
+
+use crate::types::{router::FromData, Data};
+use core::{
+    future::{ready, Future},
+    pin::Pin,
+};
+use serde_json::Value as JsonValue;
+use std::error::Error;
+
+type BoxError = Box<dyn Error + Sync + Send + 'static>;
+
+/// Message handler, it's implemented for async functions and closures
+/// whose arguments are extractors (look at [`FromData`]).
+pub trait Handler<Args>: Sync + Send + 'static {
+    /// Extract arguments from `data` and call handler.
+    fn call(
+        &self,
+        data: Data<JsonValue>,
+    ) -> Pin<Box<dyn Future<Output = Result<(), BoxError>> + Send>>;
+}
+
+/// Result of handler, it's implemented for `()` and `Result<(), E>`.
+pub trait HandlerResult {
+    /// Convert handler result, error is reported to event channel.
+    fn into_result(self) -> Result<(), BoxError>;
+}
+
+impl HandlerResult for () {
+    #[inline(always)]
+    fn into_result(self) -> Result<(), BoxError> {
+        Ok(())
+    }
+}
+
+impl<E: Into<BoxError>> HandlerResult for Result<(), E> {
+    #[inline(always)]
+    fn into_result(self) -> Result<(), BoxError> {
+        self.map_err(Into::into)
+    }
+}
+
+macro_rules! impl_handler {
+    ($($arg:ident),*) => {
+        impl<F, Fut, R, $($arg,)*> Handler<($($arg,)*)> for F
+        where
+            F: Fn($($arg),*) -> Fut + Sync + Send + 'static,
+            Fut: Future<Output = R> + Send + 'static,
+            R: HandlerResult,
+            $($arg: FromData,)*
+        {
+            #[allow(non_snake_case, unused_variables)]
+            fn call(
+                &self,
+                data: Data<JsonValue>,
+            ) -> Pin<Box<dyn Future<Output = Result<(), BoxError>> + Send>> {
+                $(
+                    let $arg = match $arg::from_data(&data) {
+                        Ok(arg) => arg,
+                        Err(error) => return Box::pin(ready(Err(error.into()))),
+                    };
+                )*
+                let future = self($($arg),*);
+
+                Box::pin(async move { future.await.into_result() })
+            }
+        }
+    };
+}
+
+impl_handler!();
+impl_handler!(A1);
+impl_handler!(A1, A2);
+impl_handler!(A1, A2, A3);
+impl_handler!(A1, A2, A3, A4);
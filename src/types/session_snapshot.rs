@@ -0,0 +1,37 @@
+use crate::types::{Advice, ChannelId};
+use serde::{Deserialize, Serialize};
+
+/// State of cometd session which can be saved and later resumed
+/// through [`crate::CometdClientBuilder::resume`] (e.g. after process restart).
+///
+/// # Example
+/// ```rust,no_run
+/// # use cometd_client::{CometdClient, CometdClientBuilder, types::SessionSnapshot};
+/// # async fn _fun() -> Result<(), Box<dyn std::error::Error>> {
+/// # let client: CometdClient<()> = CometdClientBuilder::new(&"http://[::1]:1025/".parse()?).build()?;
+/// let snapshot = serde_json::to_string(&client.into_snapshot())?;
+///
+/// // after restart
+/// let snapshot = serde_json::from_str::<SessionSnapshot>(&snapshot)?;
+/// let client = CometdClientBuilder::new(&"http://[::1]:1025/".parse()?)
+///     .resume(snapshot)
+///     .build()?;
+/// # let _client: CometdClient<()> = client;
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Default, Clone, Serialize, Deserialize)]
+pub struct SessionSnapshot {
+    /// Client id which was given by server in handshake.
+    pub client_id: Option<String>,
+    /// Cookies in `Set-Cookie` header format, so their attributes (e.g. domain, path
+    /// and expiration time) are kept.
+    pub cookies: Vec<String>,
+    /// Channels which client is subscribed to.
+    ///
+    /// They are resumed without subscription handles, so their messages are sent
+    /// to event channel and they are kept until [`crate::CometdClient::unsubscribe`].
+    pub subscriptions: Vec<ChannelId>,
+    /// Last advice from server.
+    pub advice: Advice,
+}
@@ -0,0 +1,82 @@
+mod common;
+
+use cometd_client::CometdClientBuilder;
+use common::{events_until_closed, next_error, MockServer};
+use core::time::Duration;
+use serde_json::{json, Value};
+use tokio::time::timeout;
+
+#[tokio::test]
+async fn test_advice_interval() {
+    let server = MockServer::start().await;
+    server.state().hold = Duration::ZERO;
+    server.state().connect_advice = json!({ "reconnect": "retry", "interval": 200 });
+    let _client = CometdClientBuilder::new(&server.url)
+        .build::<Value>()
+        .unwrap();
+
+    server
+        .wait_for(|server| server.received("/meta/connect").len() == 3)
+        .await;
+
+    let state = server.state();
+    let connects = state
+        .received
+        .iter()
+        .filter(|(_, message)| message["channel"] == "/meta/connect")
+        .map(|(time, _)| *time)
+        .collect::<Vec<_>>();
+    // first connect follows handshake advice without interval
+    assert!(connects[2] - connects[1] >= Duration::from_millis(200));
+}
+
+#[tokio::test]
+async fn test_advice_timeout() {
+    let server = MockServer::start().await;
+    // server holds connect longer than request timeout, but advises to wait for it
+    server.state().hold = Duration::from_millis(300);
+    let client = CometdClientBuilder::new(&server.url)
+        .request_timeout(Duration::from_millis(100))
+        .build::<Value>()
+        .unwrap();
+    let mut events = client.rx();
+
+    server
+        .wait_for(|server| server.received("/meta/connect").len() == 3)
+        .await;
+
+    assert!(timeout(Duration::from_millis(10), next_error(&mut events))
+        .await
+        .is_err());
+}
+
+#[tokio::test]
+async fn test_advice_reconnect_none() {
+    let server = MockServer::start().await;
+    server.state().connect_advice = json!({ "reconnect": "none" });
+    let client = CometdClientBuilder::new(&server.url)
+        .build::<Value>()
+        .unwrap();
+
+    // client stops without disconnect request
+    let events = events_until_closed(client.rx()).await;
+    assert!(events.is_empty());
+    assert_eq!(server.received("/meta/connect").len(), 1);
+    assert!(server.received("/meta/disconnect").is_empty());
+}
+
+#[tokio::test]
+async fn test_advice_reconnect_applies_only_to_its_reply() {
+    let server = MockServer::start().await;
+    server.state().hold = Duration::ZERO;
+    server.state().handshake_advice = json!({ "reconnect": "none", "interval": 0 });
+    let client = CometdClientBuilder::new(&server.url)
+        .build::<Value>()
+        .unwrap();
+
+    // connect replies without advice don't repeat handshake advice
+    server
+        .wait_for(|server| server.received("/meta/connect").len() >= 3)
+        .await;
+    assert!(client.subscribe("/topic/a").await.is_ok());
+}
@@ -0,0 +1,43 @@
+use cometd_client::types::{Backoff, Jitter};
+use core::time::Duration;
+
+const INITIAL_DELAY: Duration = Duration::from_millis(100);
+const MAX_DELAY: Duration = Duration::from_secs(1);
+
+#[test]
+fn test_backoff_without_jitter() {
+    let delays = Backoff::new(INITIAL_DELAY, MAX_DELAY)
+        .jitter(Jitter::None)
+        .iter()
+        .take(6)
+        .collect::<Vec<_>>();
+
+    assert_eq!(
+        delays,
+        [
+            Duration::from_millis(100),
+            Duration::from_millis(200),
+            Duration::from_millis(400),
+            Duration::from_millis(800),
+            MAX_DELAY,
+            MAX_DELAY,
+        ]
+    );
+}
+
+#[test]
+fn test_backoff_with_jitter() {
+    let full = Backoff::new(INITIAL_DELAY, MAX_DELAY).jitter(Jitter::Full);
+    let decorrelated = Backoff::new(INITIAL_DELAY, MAX_DELAY).jitter(Jitter::Decorrelated);
+
+    for (attempt, delay) in full.iter().take(100).enumerate() {
+        let upper_bound = INITIAL_DELAY
+            .saturating_mul(1 << attempt.min(10))
+            .min(MAX_DELAY);
+        assert!(delay <= upper_bound, "{delay:?} > {upper_bound:?}");
+    }
+
+    for delay in decorrelated.iter().take(100) {
+        assert!((INITIAL_DELAY..=MAX_DELAY).contains(&delay), "{delay:?}");
+    }
+}
@@ -0,0 +1,71 @@
+mod common;
+
+use cometd_client::{
+    types::{CometdError, ErrorKind},
+    CometdClientBuilder,
+};
+use common::MockServer;
+use core::time::Duration;
+use serde_json::{json, Value};
+use tokio::time::timeout;
+
+#[tokio::test]
+async fn test_call() {
+    let server = MockServer::start().await;
+    let client = CometdClientBuilder::new(&server.url)
+        .build::<Value>()
+        .unwrap();
+    let mut events = client.rx();
+
+    // reply in response to request
+    let echo: Value = client
+        .call("/service/echo", json!({ "a": 1 }), Duration::from_secs(5))
+        .await
+        .unwrap();
+    assert_eq!(echo, json!({ "a": 1 }));
+
+    // reply is delivered through connect
+    let later: Vec<i64> = client
+        .call("/service/later", [1, 2, 3], Duration::from_secs(5))
+        .await
+        .unwrap();
+    assert_eq!(later, [1, 2, 3]);
+
+    // replies aren't sent to event channel
+    assert!(timeout(Duration::from_millis(200), events.recv())
+        .await
+        .is_err());
+}
+
+#[tokio::test]
+async fn test_call_timeout() {
+    let server = MockServer::start().await;
+    let client = CometdClientBuilder::new(&server.url)
+        .build::<Value>()
+        .unwrap();
+
+    let result = client
+        .call::<_, Value>("/service/never", 1, Duration::from_millis(300))
+        .await;
+
+    assert!(matches!(
+        result,
+        Err(CometdError::RequestTimeout(ErrorKind::Call))
+    ));
+    assert_eq!(server.received("/service/never").len(), 1);
+}
+
+#[tokio::test]
+async fn test_call_to_broadcast_channel() {
+    let server = MockServer::start().await;
+    let client = CometdClientBuilder::new(&server.url)
+        .build::<Value>()
+        .unwrap();
+
+    let result = client
+        .call::<_, Value>("/topic/a", 1, Duration::from_secs(5))
+        .await;
+
+    assert!(matches!(result, Err(CometdError::InvalidChannel(..))));
+    assert!(server.received("/topic/a").is_empty());
+}
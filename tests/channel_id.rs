@@ -0,0 +1,58 @@
+use cometd_client::types::{ChannelId, CometdError};
+
+#[test]
+fn test_channel_id_validation() {
+    for channel in [
+        "/topic",
+        "/orders/1/items",
+        "/prices/*",
+        "/**",
+        "/a-b_c/$x@y",
+        "/topic/Order:Created",
+        "/data/Account%20Change/é",
+    ] {
+        assert!(channel.parse::<ChannelId>().is_ok(), "`{channel}`");
+    }
+
+    for channel in [
+        "",
+        "/",
+        "topic",
+        "meta/x",
+        "/foo//bar",
+        "/foo/",
+        "/*/bar",
+        "/f oo",
+        "/foo/\tbar",
+        "/foo/\u{7f}",
+    ] {
+        assert!(
+            matches!(
+                channel.parse::<ChannelId>(),
+                Err(CometdError::InvalidChannel(..))
+            ),
+            "`{channel}`"
+        );
+    }
+}
+
+#[test]
+fn test_channel_id_kinds() {
+    let meta = ChannelId::new("/meta/connect").unwrap();
+    let service = ChannelId::new("/service/echo").unwrap();
+    let broadcast = ChannelId::new("/topic/**").unwrap();
+
+    assert!(meta.is_meta() && !meta.is_service() && !meta.is_broadcast());
+    assert!(service.is_service() && !service.is_meta() && !service.is_broadcast());
+    assert!(broadcast.is_broadcast() && broadcast.is_wildcard());
+    assert!(broadcast.matches("/topic/a/b"));
+}
+
+#[test]
+fn test_channel_id_serde() {
+    let channel = serde_json::from_str::<ChannelId>(r#""/topic/0""#).unwrap();
+
+    assert_eq!(channel, "/topic/0");
+    assert_eq!(serde_json::to_string(&channel).unwrap(), r#""/topic/0""#);
+    assert!(serde_json::from_str::<ChannelId>(r#""topic""#).is_err());
+}
@@ -0,0 +1,27 @@
+use cometd_client::types::ChannelPattern;
+
+#[test]
+fn test_channel_pattern_matches() {
+    let cases = [
+        ("/orders", "/orders", true),
+        ("/orders", "/orders/1", false),
+        ("/prices/*", "/prices/btc", true),
+        ("/prices/*", "/prices/btc/usd", false),
+        ("/prices/*", "/prices", false),
+        ("/orders/**", "/orders/1", true),
+        ("/orders/**", "/orders/1/items/2", true),
+        ("/orders/**", "/orders", false),
+        ("/orders/**", "/prices/1", false),
+        ("/*/1", "/orders/1", true),
+        ("/**/items", "/orders/1/items", true),
+        ("/**/items", "/items", false),
+    ];
+
+    for (pattern, channel, expected) in cases {
+        assert_eq!(
+            ChannelPattern::new(pattern).matches(channel),
+            expected,
+            "`{pattern}` with `{channel}`"
+        );
+    }
+}
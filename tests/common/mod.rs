@@ -0,0 +1,421 @@
+//! Local CometD server which is used by integration tests.
+//!
+//! It keeps sessions and subscriptions in memory, holds connect requests until there are
+//! messages for client and logs every received message, so tests can check what client sent.
+#![allow(dead_code)]
+
+use cometd_client::types::{CometdClientEvent, CometdError, CometdEventReceiver};
+use hyper::{
+    header::UPGRADE,
+    service::{make_service_fn, service_fn},
+    Body, Method, Request, Response, Server, StatusCode,
+};
+use serde_json::{json, Value};
+use std::{
+    collections::{HashMap, HashSet},
+    convert::Infallible,
+    sync::{Arc, Mutex, MutexGuard},
+    time::{Duration, Instant},
+};
+use tokio::time::timeout;
+use url::Url;
+
+pub const WAIT_TIMEOUT: Duration = Duration::from_secs(5);
+
+#[derive(Debug)]
+pub struct State {
+    next_client_id: u64,
+    sessions: HashMap<String, Session>,
+    /// Every received message with time when it was received.
+    pub received: Vec<(Instant, Value)>,
+    pub connection_types: Vec<&'static str>,
+    pub handshake_advice: Value,
+    /// Advice which is sent with every connect reply, `null` means none.
+    pub connect_advice: Value,
+    /// Reply to every connect request with `successful: false`.
+    pub connect_denied: bool,
+    /// Subscriptions to these channels are denied with `403::Forbidden`.
+    pub forbidden: HashSet<String>,
+    /// How long connect request is held if there is nothing to deliver.
+    pub hold: Duration,
+    /// Reply with `503 Service Unavailable` to every request.
+    pub unavailable: bool,
+    /// Reply with body which isn't JSON to every request.
+    pub garbage: bool,
+}
+
+#[derive(Debug, Default)]
+struct Session {
+    subscriptions: HashSet<String>,
+    queue: Vec<Value>,
+}
+
+impl Default for State {
+    fn default() -> Self {
+        Self {
+            next_client_id: 0,
+            sessions: HashMap::new(),
+            received: Vec::new(),
+            connection_types: vec!["long-polling", "callback-polling", "websocket"],
+            handshake_advice: json!({ "reconnect": "retry", "interval": 0, "timeout": 1000 }),
+            connect_advice: Value::Null,
+            connect_denied: false,
+            forbidden: HashSet::from(["/forbidden".to_owned()]),
+            hold: Duration::from_millis(100),
+            unavailable: false,
+            garbage: false,
+        }
+    }
+}
+
+type SharedState = Arc<Mutex<State>>;
+
+#[derive(Debug, Clone)]
+pub struct MockServer {
+    pub url: Url,
+    state: SharedState,
+}
+
+impl MockServer {
+    /// Start server on random local port.
+    pub async fn start() -> Self {
+        let state = SharedState::default();
+        let service_state = Arc::clone(&state);
+        let make_service = make_service_fn(move |_| {
+            let state = Arc::clone(&service_state);
+            async move {
+                Ok::<_, Infallible>(service_fn(move |request| {
+                    handle(Arc::clone(&state), request)
+                }))
+            }
+        });
+
+        let server = Server::bind(&([127, 0, 0, 1], 0).into()).serve(make_service);
+        let url = format!("http://{}/cometd/", server.local_addr())
+            .parse()
+            .unwrap();
+        tokio::spawn(server);
+
+        Self { url, state }
+    }
+
+    pub fn state(&self) -> MutexGuard<'_, State> {
+        self.state.lock().unwrap()
+    }
+
+    /// Return received messages which were sent to `channel`.
+    pub fn received(&self, channel: &str) -> Vec<Value> {
+        self.state()
+            .received
+            .iter()
+            .map(|(_, message)| message)
+            .filter(|message| message["channel"] == channel)
+            .cloned()
+            .collect()
+    }
+
+    /// Return how many sessions are subscribed to `channel`.
+    pub fn subscribers(&self, channel: &str) -> usize {
+        self.state()
+            .sessions
+            .values()
+            .filter(|session| session.subscriptions.contains(channel))
+            .count()
+    }
+
+    /// Forget all sessions like after server restart.
+    pub fn drop_sessions(&self) {
+        self.state().sessions.clear();
+    }
+
+    /// Deliver message to every session which is subscribed to `channel`.
+    pub fn publish(&self, channel: &str, data: Value) {
+        deliver(&mut self.state(), channel, &data);
+    }
+
+    /// Wait until `condition` is true.
+    pub async fn wait_for(&self, condition: impl Fn(&Self) -> bool) {
+        timeout(WAIT_TIMEOUT, async {
+            while !condition(self) {
+                tokio::time::sleep(Duration::from_millis(10)).await;
+            }
+        })
+        .await
+        .expect("condition wasn't met");
+    }
+}
+
+/// Wait for next error from event channel, messages are skipped.
+pub async fn next_error<Msg>(rx: &mut CometdEventReceiver<Msg>) -> Arc<CometdError> {
+    timeout(WAIT_TIMEOUT, async {
+        loop {
+            match rx.recv().await.expect("event channel was closed") {
+                CometdClientEvent::Error(error) => break error,
+                CometdClientEvent::Message(_) => {}
+            }
+        }
+    })
+    .await
+    .expect("no error was received")
+}
+
+/// Wait for next message from event channel, errors are skipped.
+pub async fn next_message<Msg: Clone>(rx: &mut CometdEventReceiver<Msg>) -> Option<Msg> {
+    timeout(WAIT_TIMEOUT, async {
+        loop {
+            match rx.recv().await.expect("event channel was closed") {
+                CometdClientEvent::Message(batch) => {
+                    break batch.first().and_then(|data| data.message.clone())
+                }
+                CometdClientEvent::Error(_) => {}
+            }
+        }
+    })
+    .await
+    .expect("no message was received")
+}
+
+/// Collect events until client is stopped.
+pub async fn events_until_closed<Msg>(
+    mut rx: CometdEventReceiver<Msg>,
+) -> Vec<CometdClientEvent<Msg>> {
+    timeout(WAIT_TIMEOUT, async {
+        let mut events = Vec::new();
+        while let Some(event) = rx.recv().await {
+            events.push(event);
+        }
+        events
+    })
+    .await
+    .expect("client wasn't stopped")
+}
+
+async fn handle(state: SharedState, request: Request<Body>) -> Result<Response<Body>, Infallible> {
+    let (unavailable, garbage) = {
+        let state = state.lock().unwrap();
+        (state.unavailable, state.garbage)
+    };
+    if unavailable {
+        return Ok(with_status(StatusCode::SERVICE_UNAVAILABLE));
+    }
+    if garbage {
+        return Ok(Response::new(Body::from("not json")));
+    }
+    // websocket upgrade is refused like by proxy
+    if request.headers().contains_key(UPGRADE) {
+        let now = Instant::now();
+        state
+            .lock()
+            .unwrap()
+            .received
+            .push((now, json!({ "upgrade": "websocket" })));
+        return Ok(with_status(StatusCode::FORBIDDEN));
+    }
+
+    // callback-polling
+    if request.method() == Method::GET {
+        let query = request.uri().query().unwrap_or_default().to_owned();
+        let mut messages = None;
+        let mut callback = None;
+        for (key, value) in url::form_urlencoded::parse(query.as_bytes()) {
+            match &*key {
+                "message" => messages = Some(serde_json::from_str(&value).unwrap()),
+                "jsonp" => callback = Some(value.into_owned()),
+                _ => {}
+            }
+        }
+
+        let callback = callback.unwrap();
+        let now = Instant::now();
+        state
+            .lock()
+            .unwrap()
+            .received
+            .push((now, json!({ "jsonp": callback })));
+
+        let replies = process(&state, messages.unwrap()).await;
+        let body = format!("{callback}({});", Value::Array(replies));
+        return Ok(Response::new(Body::from(body)));
+    }
+
+    let body = hyper::body::to_bytes(request.into_body()).await.unwrap();
+    let replies = process(&state, serde_json::from_slice(&body).unwrap()).await;
+
+    Ok(Response::new(Body::from(Value::Array(replies).to_string())))
+}
+
+async fn process(state: &SharedState, messages: Vec<Value>) -> Vec<Value> {
+    let mut replies = Vec::new();
+
+    for message in messages {
+        state
+            .lock()
+            .unwrap()
+            .received
+            .push((Instant::now(), message.clone()));
+
+        let id = &message["id"];
+        let channel = message["channel"].as_str().unwrap_or_default();
+        let client_id = message["clientId"].as_str().unwrap_or_default();
+
+        match channel {
+            "/meta/handshake" => {
+                let mut state = state.lock().unwrap();
+                state.next_client_id += 1;
+                let client_id = format!("client-{}", state.next_client_id);
+                state.sessions.insert(client_id.clone(), Session::default());
+
+                replies.push(json!({
+                    "id": id,
+                    "channel": channel,
+                    "successful": true,
+                    "clientId": client_id,
+                    "version": "1.0",
+                    "supportedConnectionTypes": state.connection_types,
+                    "advice": state.handshake_advice,
+                }));
+            }
+            _ if channel.starts_with("/meta/")
+                && !state.lock().unwrap().sessions.contains_key(client_id) =>
+            {
+                replies.push(json!({
+                    "id": id,
+                    "channel": channel,
+                    "successful": false,
+                    "subscription": message["subscription"],
+                    "error": "402::Unknown client",
+                    "advice": { "reconnect": "handshake" },
+                }));
+            }
+            "/meta/connect" => {
+                let hold = state.lock().unwrap().hold;
+                let started = Instant::now();
+                loop {
+                    {
+                        let mut state = state.lock().unwrap();
+                        let queue = &mut state.sessions.get_mut(client_id).unwrap().queue;
+                        if !queue.is_empty() || started.elapsed() >= hold {
+                            replies.append(queue);
+                            break;
+                        }
+                    }
+                    tokio::time::sleep(Duration::from_millis(10)).await;
+                }
+
+                let (advice, denied) = {
+                    let state = state.lock().unwrap();
+                    (state.connect_advice.clone(), state.connect_denied)
+                };
+                let mut reply = json!({ "id": id, "channel": channel, "successful": !denied });
+                if denied {
+                    reply["error"] = json!("500::Denied");
+                }
+                if !advice.is_null() {
+                    reply["advice"] = advice;
+                }
+                replies.push(reply);
+            }
+            "/meta/subscribe" | "/meta/unsubscribe" => {
+                let mut state = state.lock().unwrap();
+                let subscription = message["subscription"].as_str().unwrap().to_owned();
+
+                if channel == "/meta/subscribe" && state.forbidden.contains(&subscription) {
+                    replies.push(json!({
+                        "id": id,
+                        "channel": channel,
+                        "successful": false,
+                        "subscription": subscription,
+                        "error": "403::Forbidden",
+                    }));
+                    continue;
+                }
+
+                let subscriptions = &mut state.sessions.get_mut(client_id).unwrap().subscriptions;
+                if channel == "/meta/subscribe" {
+                    subscriptions.insert(subscription.clone());
+                } else {
+                    subscriptions.remove(&subscription);
+                }
+                replies.push(json!({
+                    "id": id,
+                    "channel": channel,
+                    "successful": true,
+                    "subscription": subscription,
+                }));
+            }
+            "/meta/disconnect" => {
+                state.lock().unwrap().sessions.remove(client_id);
+                replies.push(json!({ "id": id, "channel": channel, "successful": true }));
+            }
+            // replies with request data
+            "/service/echo" => {
+                replies.push(json!({
+                    "id": id,
+                    "channel": channel,
+                    "successful": true,
+                    "data": message["data"],
+                }));
+            }
+            // replies with request data through connect
+            "/service/later" => {
+                let mut state = state.lock().unwrap();
+                if let Some(session) = state.sessions.get_mut(client_id) {
+                    session.queue.push(json!({
+                        "id": id,
+                        "channel": channel,
+                        "successful": true,
+                        "data": message["data"],
+                    }));
+                }
+                replies.push(json!({ "id": id, "channel": channel, "successful": true }));
+            }
+            // `/service/never` and other service channels are only acknowledged
+            _ if channel.starts_with("/service/") => {
+                replies.push(json!({ "id": id, "channel": channel, "successful": true }));
+            }
+            _ => {
+                deliver(&mut state.lock().unwrap(), channel, &message["data"]);
+                replies.push(json!({ "id": id, "channel": channel, "successful": true }));
+            }
+        }
+    }
+
+    replies
+}
+
+fn deliver(state: &mut State, channel: &str, data: &Value) {
+    for session in state.sessions.values_mut() {
+        if session
+            .subscriptions
+            .iter()
+            .any(|subscription| matches(subscription, channel))
+        {
+            session
+                .queue
+                .push(json!({ "channel": channel, "data": data }));
+        }
+    }
+}
+
+/// Bayeux glob matching: `*` matches one segment, `**` matches any number of segments.
+fn matches(subscription: &str, channel: &str) -> bool {
+    if let Some(prefix) = subscription.strip_suffix("/**") {
+        channel
+            .strip_prefix(prefix)
+            .is_some_and(|rest| rest.len() > 1 && rest.starts_with('/'))
+    } else if let Some(prefix) = subscription.strip_suffix("/*") {
+        channel
+            .strip_prefix(prefix)
+            .and_then(|rest| rest.strip_prefix('/'))
+            .is_some_and(|segment| !segment.is_empty() && !segment.contains('/'))
+    } else {
+        subscription == channel
+    }
+}
+
+fn with_status(status: StatusCode) -> Response<Body> {
+    Response::builder()
+        .status(status)
+        .body(Body::empty())
+        .unwrap()
+}
@@ -0,0 +1,119 @@
+mod common;
+
+use cometd_client::{
+    types::{CometdError, ConnectionType},
+    CometdClientBuilder,
+};
+use common::{MockServer, WAIT_TIMEOUT};
+use serde_json::{json, Value};
+use tokio::time::timeout;
+
+#[test]
+fn test_connection_type_names() {
+    for connection_type in [
+        ConnectionType::LongPolling,
+        ConnectionType::CallbackPolling,
+        ConnectionType::WebSocket,
+    ] {
+        let name = serde_json::to_value(connection_type).unwrap();
+
+        assert_eq!(name, connection_type.as_str());
+        assert_eq!(
+            serde_json::from_value::<ConnectionType>(name).unwrap(),
+            connection_type
+        );
+    }
+}
+
+#[test]
+fn test_empty_connection_types() {
+    let result = CometdClientBuilder::new(&"http://[::1]:1025/".parse().unwrap())
+        .connection_types(&[])
+        .build::<()>();
+
+    assert!(matches!(result, Err(CometdError::MissingConnectionType)));
+}
+
+#[tokio::test]
+async fn test_websocket_fallback() {
+    let server = MockServer::start().await;
+    let client = CometdClientBuilder::new(&server.url)
+        .connection_types(&[ConnectionType::WebSocket, ConnectionType::LongPolling])
+        .build::<Value>()
+        .unwrap();
+
+    // server refuses websocket upgrade
+    let mut subscription = client.subscribe("/topic/a").await.unwrap();
+    assert_eq!(client.connection_type(), ConnectionType::LongPolling);
+    assert!(server
+        .state()
+        .received
+        .iter()
+        .any(|(_, message)| message["upgrade"] == "websocket"));
+
+    client.publish("/topic/a", 42).await.unwrap();
+    let data = timeout(WAIT_TIMEOUT, subscription.recv())
+        .await
+        .unwrap()
+        .unwrap()
+        .unwrap();
+    assert_eq!(data.message, Some(json!(42)));
+    // connect which was refused is resent through long-polling
+    assert!(server
+        .received("/meta/connect")
+        .iter()
+        .all(|message| message["connectionType"] == "long-polling"));
+}
+
+#[tokio::test]
+async fn test_unsupported_connection_type_is_skipped() {
+    let server = MockServer::start().await;
+    server.state().connection_types = vec!["long-polling"];
+    let client = CometdClientBuilder::new(&server.url)
+        .connection_types(&[ConnectionType::WebSocket, ConnectionType::LongPolling])
+        .build::<Value>()
+        .unwrap();
+
+    client.subscribe_batch(&["/topic/a"]).await.unwrap();
+
+    assert_eq!(client.connection_type(), ConnectionType::LongPolling);
+    assert!(server
+        .state()
+        .received
+        .iter()
+        .all(|(_, message)| message["upgrade"].is_null()));
+}
+
+#[tokio::test]
+async fn test_callback_polling() {
+    let server = MockServer::start().await;
+    let client = CometdClientBuilder::new(&server.url)
+        .connection_types(&[ConnectionType::CallbackPolling])
+        .build::<Value>()
+        .unwrap();
+
+    let mut subscription = client.subscribe("/topic/a").await.unwrap();
+    client.publish("/topic/a", 42).await.unwrap();
+    let data = timeout(WAIT_TIMEOUT, subscription.recv())
+        .await
+        .unwrap()
+        .unwrap()
+        .unwrap();
+
+    assert_eq!(data.message, Some(json!(42)));
+    assert_eq!(client.connection_type(), ConnectionType::CallbackPolling);
+    // every message is sent in its own GET request
+    let state = server.state();
+    let requests = state
+        .received
+        .iter()
+        .filter(|(_, message)| message["jsonp"].is_string())
+        .count();
+    let messages = state
+        .received
+        .iter()
+        .filter(|(_, message)| message["channel"].is_string())
+        .count();
+    assert!(requests > 0);
+    assert_eq!(requests, messages);
+}
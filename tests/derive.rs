@@ -0,0 +1,69 @@
+use cometd_client::types::{ChannelId, CometdError, CometdMessage};
+use serde::Deserialize;
+use serde_json::json;
+
+#[derive(Debug, PartialEq, Deserialize)]
+struct Order {
+    id: u64,
+}
+
+#[derive(Debug, PartialEq, Deserialize)]
+struct Price {
+    id: u64,
+    value: f64,
+}
+
+#[derive(Debug, PartialEq, CometdMessage)]
+enum Msg {
+    #[cometd(channel = "/orders/**")]
+    Order(Order),
+    #[cometd(channel = "/prices/*")]
+    Price(Price),
+    #[cometd(channel = "/heartbeat")]
+    Heartbeat,
+}
+
+#[test]
+fn test_derive_cometd_message() {
+    let from_data = |channel: &str, data| {
+        let channel = ChannelId::new(channel).unwrap();
+        Msg::from_data(Some(&channel), data)
+    };
+    let price = json!({ "id": 1, "value": 2.5 });
+
+    assert_eq!(
+        from_data("/orders/eu/1", price.clone()).unwrap(),
+        Msg::Order(Order { id: 1 })
+    );
+    assert_eq!(
+        from_data("/prices/btc", price.clone()).unwrap(),
+        Msg::Price(Price { id: 1, value: 2.5 })
+    );
+    assert_eq!(
+        from_data("/heartbeat", json!(null)).unwrap(),
+        Msg::Heartbeat
+    );
+    assert!(matches!(
+        from_data("/prices/btc", json!({ "id": 1 })),
+        Err(CometdError::ParseBody(..))
+    ));
+    assert!(matches!(
+        from_data("/prices/btc/usd", price),
+        Err(CometdError::UnmatchedChannel(channel, "Msg")) if &*channel == "/prices/btc/usd"
+    ));
+}
+
+#[test]
+fn test_derive_cometd_message_without_data() {
+    let from_empty = |channel: &str| {
+        let channel = ChannelId::new(channel).unwrap();
+        Msg::from_empty(Some(&channel))
+    };
+
+    assert_eq!(from_empty("/heartbeat").unwrap(), Some(Msg::Heartbeat));
+    assert_eq!(from_empty("/orders/eu/1").unwrap(), None);
+    assert!(matches!(
+        from_empty("/unknown"),
+        Err(CometdError::UnmatchedChannel(channel, "Msg")) if &*channel == "/unknown"
+    ));
+}
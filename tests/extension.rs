@@ -0,0 +1,110 @@
+use cometd_client::types::{
+    extension::{
+        AckExtension, FileReplayStore, MemoryReplayStore, ReplayExtension, ReplayStore,
+        TimesyncExtension,
+    },
+    Extension, Message,
+};
+use core::time::Duration;
+use serde_json::json;
+
+fn message(channel: &str, ext: serde_json::Value) -> Message {
+    serde_json::from_value(json!({ "channel": channel, "successful": true, "ext": ext })).unwrap()
+}
+
+#[test]
+fn test_ack_extension() {
+    let ack = AckExtension::new();
+
+    let mut handshake = Message {
+        channel: Some("/meta/handshake".to_owned()),
+        ..Default::default()
+    };
+    assert!(ack.outgoing_meta(&mut handshake));
+    assert_eq!(handshake.ext.unwrap()["ack"], json!(true));
+    assert!(ack.incoming_meta(&mut message("/meta/handshake", json!({ "ack": true }))));
+
+    let mut connect = Message {
+        channel: Some("/meta/connect".to_owned()),
+        ..Default::default()
+    };
+    assert!(ack.outgoing_meta(&mut connect));
+    assert_eq!(connect.ext.unwrap()["ack"], json!(0));
+
+    assert!(ack.incoming_meta(&mut message("/meta/connect", json!({ "ack": 5 }))));
+    assert_eq!(ack.batch(), 5);
+}
+
+#[test]
+fn test_timesync_extension() {
+    let timesync = TimesyncExtension::new();
+
+    let mut connect = Message {
+        channel: Some("/meta/connect".to_owned()),
+        ..Default::default()
+    };
+    assert!(timesync.outgoing_meta(&mut connect));
+    let tc = connect.ext.unwrap()["timesync"]["tc"].as_i64().unwrap();
+
+    let ext = json!({ "timesync": { "tc": tc, "ts": tc + 1_000, "p": 0 } });
+    assert!(timesync.incoming_meta(&mut message("/meta/connect", ext)));
+
+    assert!((900..=1_000).contains(&timesync.offset()));
+    assert!(timesync.lag() < Duration::from_millis(100));
+}
+
+#[test]
+fn test_replay_extension() {
+    let replay = ReplayExtension::new(MemoryReplayStore::new());
+    assert!(replay.incoming_meta(&mut message("/meta/handshake", json!({ "replay": true }))));
+
+    let mut data = Message {
+        channel: Some("/topic/orders".to_owned()),
+        data: Some(json!({ "event": { "replayId": 42 } })),
+        ..Default::default()
+    };
+    assert!(replay.incoming(&mut data));
+
+    let mut subscribe = Message {
+        channel: Some("/meta/subscribe".to_owned()),
+        subscription: Some("/topic/orders".to_owned()),
+        ..Default::default()
+    };
+    assert!(replay.outgoing_meta(&mut subscribe));
+    assert_eq!(
+        subscribe.ext.unwrap()["replay"],
+        json!({ "/topic/orders": 42 })
+    );
+}
+
+#[test]
+fn test_file_replay_store() {
+    let path = std::env::temp_dir().join(format!("cometd-replay-{}.json", std::process::id()));
+
+    let store = FileReplayStore::new(&path).unwrap();
+    assert_eq!(store.load("/topic/orders"), None);
+    store.save("/topic/orders", 42);
+
+    let store = FileReplayStore::new(&path).unwrap();
+    assert_eq!(store.load("/topic/orders"), Some(42));
+
+    std::fs::remove_file(path).unwrap();
+}
+
+#[tokio::test]
+async fn test_file_replay_store_in_runtime() {
+    let path = std::env::temp_dir().join(format!("cometd-replay-rt-{}.json", std::process::id()));
+
+    let store = FileReplayStore::new(&path).unwrap();
+    store.save("/topic/orders", 42);
+    store.save("/topic/orders", 43);
+    store.save("/topic/accounts", 7);
+    store.flush().unwrap();
+
+    let reloaded = FileReplayStore::new(&path).unwrap();
+    assert_eq!(reloaded.load("/topic/orders"), Some(43));
+    assert_eq!(reloaded.load("/topic/accounts"), Some(7));
+
+    drop((store, reloaded));
+    std::fs::remove_file(path).unwrap();
+}
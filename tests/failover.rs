@@ -0,0 +1,76 @@
+mod common;
+
+use cometd_client::{
+    types::{retry_policy::TransportRetry, Backoff},
+    CometdClientBuilder,
+};
+use common::MockServer;
+use core::time::Duration;
+use serde_json::{json, Value};
+
+#[tokio::test]
+async fn test_failover_to_next_endpoint() {
+    let server = MockServer::start().await;
+    // nothing listens there
+    let endpoints = [
+        "http://127.0.0.1:1/cometd/".parse().unwrap(),
+        server.url.clone(),
+    ];
+    let client = CometdClientBuilder::with_endpoints(&endpoints)
+        .retry_policy(TransportRetry::new(
+            3,
+            Backoff::new(Duration::from_millis(10), Duration::from_millis(50)),
+        ))
+        .build::<Value>()
+        .unwrap();
+
+    client.subscribe_batch(&["/topic/a"]).await.unwrap();
+
+    assert_eq!(client.endpoint(), server.url);
+    assert_eq!(server.subscribers("/topic/a"), 1);
+}
+
+#[tokio::test]
+async fn test_failover_to_advised_host() {
+    let first = MockServer::start().await;
+    let second = MockServer::start().await;
+    let second_host = format!(
+        "{}:{}",
+        second.url.host_str().unwrap(),
+        second.url.port().unwrap()
+    );
+    first.state().handshake_advice = json!({ "reconnect": "retry", "hosts": [second_host] });
+    let client = CometdClientBuilder::new(&first.url)
+        .supervised(true)
+        .backoff(Backoff::new(
+            Duration::from_millis(10),
+            Duration::from_millis(50),
+        ))
+        .build::<Value>()
+        .unwrap();
+    client.subscribe_batch(&["/topic/a"]).await.unwrap();
+
+    first.state().unavailable = true;
+    // session is valid only on first server, so client does handshake and resubscribes
+    second
+        .wait_for(|server| server.subscribers("/topic/a") == 1)
+        .await;
+
+    assert_eq!(client.endpoint(), second.url);
+}
+
+#[tokio::test]
+async fn test_no_failover_if_request_isnt_retried() {
+    let first = MockServer::start().await;
+    let second = MockServer::start().await;
+    let endpoints = [first.url.clone(), second.url.clone()];
+    let client = CometdClientBuilder::with_endpoints(&endpoints)
+        .build::<Value>()
+        .unwrap();
+    client.subscribe_batch(&["/topic/a"]).await.unwrap();
+
+    // default policy doesn't retry transport errors
+    first.state().unavailable = true;
+    assert!(client.publish("/topic/a", 1).await.is_err());
+    assert_eq!(client.endpoint(), first.url);
+}
@@ -0,0 +1,21 @@
+use cometd_client::types::Message;
+use serde_json::json;
+
+#[test]
+fn test_message_ext() {
+    let mut message = Message {
+        id: Some("1".to_owned()),
+        channel: Some("/meta/connect".to_owned()),
+        ..Default::default()
+    };
+    message
+        .ext
+        .get_or_insert_with(Default::default)
+        .insert("ack".to_owned(), json!(true));
+
+    assert!(message.is_meta());
+    assert_eq!(
+        serde_json::to_value(&message).unwrap(),
+        json!({ "id": "1", "channel": "/meta/connect", "ext": { "ack": true } })
+    );
+}
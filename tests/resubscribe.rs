@@ -0,0 +1,41 @@
+mod common;
+
+use cometd_client::{types::CometdError, CometdClientBuilder};
+use common::{next_error, MockServer, WAIT_TIMEOUT};
+use serde_json::{json, Value};
+use tokio::time::timeout;
+
+#[tokio::test]
+async fn test_resubscribe_after_rehandshake() {
+    let server = MockServer::start().await;
+    let client = CometdClientBuilder::new(&server.url)
+        .build::<Value>()
+        .unwrap();
+    let mut events = client.rx();
+
+    let mut subscription = client.subscribe("/topic/a").await.unwrap();
+    client.subscribe_batch(&["/topic/b"]).await.unwrap();
+
+    // server restarts and doesn't allow `/topic/b` anymore
+    server.state().forbidden.insert("/topic/b".to_owned());
+    server.drop_sessions();
+    server
+        .wait_for(|server| server.subscribers("/topic/a") == 1)
+        .await;
+
+    assert_eq!(server.received("/meta/handshake").len(), 2);
+    let error = next_error(&mut events).await;
+    assert!(matches!(
+        *error,
+        CometdError::Resubscribe(ref channel, _) if channel == "/topic/b"
+    ));
+    assert_eq!(client.subscriptions(), ["/topic/a"]);
+
+    server.publish("/topic/a", json!(42));
+    let data = timeout(WAIT_TIMEOUT, subscription.recv())
+        .await
+        .unwrap()
+        .unwrap()
+        .unwrap();
+    assert_eq!(data.message, Some(json!(42)));
+}
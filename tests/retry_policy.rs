@@ -0,0 +1,72 @@
+use cometd_client::types::{
+    retry_policy::{AdviceRetry, NoRetry, RetryDecision, TransportRetry},
+    Backoff, CometdError, ErrorKind, Jitter, Reconnect, RetryPolicy,
+};
+use core::time::Duration;
+use hyper::StatusCode;
+
+const DELAY: Duration = Duration::from_millis(10);
+const BACKOFF: Backoff = Backoff::new(DELAY, DELAY).jitter(Jitter::None);
+
+#[test]
+fn test_advice_retry() {
+    let policy = AdviceRetry::new(1, BACKOFF);
+    let handshake = CometdError::WrongResponse(ErrorKind::Connect, Reconnect::Handshake, "".into());
+    let retry = CometdError::WrongResponse(ErrorKind::Connect, Reconnect::Retry, "".into());
+    let none = CometdError::WrongResponse(ErrorKind::Connect, Reconnect::None, "".into());
+    let timeout = CometdError::RequestTimeout(ErrorKind::Connect);
+
+    assert_eq!(
+        policy.decide(&handshake, ErrorKind::Connect, 0),
+        RetryDecision::Handshake(DELAY)
+    );
+    assert_eq!(
+        policy.decide(&retry, ErrorKind::Connect, 0),
+        RetryDecision::Retry(DELAY)
+    );
+    assert_eq!(
+        policy.decide(&retry, ErrorKind::Connect, 1),
+        RetryDecision::GiveUp
+    );
+    assert_eq!(
+        policy.decide(&none, ErrorKind::Connect, 0),
+        RetryDecision::GiveUp
+    );
+    assert_eq!(
+        policy.decide(&timeout, ErrorKind::Connect, 0),
+        RetryDecision::GiveUp
+    );
+}
+
+#[test]
+fn test_transport_retry() {
+    let policy = TransportRetry::new(1, BACKOFF);
+    let timeout = CometdError::RequestTimeout(ErrorKind::Connect);
+    let unavailable = CometdError::StatusCode(
+        ErrorKind::Connect,
+        StatusCode::SERVICE_UNAVAILABLE,
+        Vec::new(),
+    );
+    let forbidden = CometdError::StatusCode(ErrorKind::Connect, StatusCode::FORBIDDEN, Vec::new());
+
+    assert_eq!(
+        policy.decide(&timeout, ErrorKind::Connect, 0),
+        RetryDecision::Retry(DELAY)
+    );
+    assert_eq!(
+        policy.decide(&unavailable, ErrorKind::Connect, 0),
+        RetryDecision::Retry(DELAY)
+    );
+    assert_eq!(
+        policy.decide(&unavailable, ErrorKind::Connect, 1),
+        RetryDecision::GiveUp
+    );
+    assert_eq!(
+        policy.decide(&forbidden, ErrorKind::Connect, 0),
+        RetryDecision::GiveUp
+    );
+    assert_eq!(
+        NoRetry.decide(&timeout, ErrorKind::Connect, 0),
+        RetryDecision::GiveUp
+    );
+}
@@ -0,0 +1,149 @@
+mod common;
+
+use cometd_client::{
+    types::{
+        router::{FromData, Json},
+        ChannelId, CometdError, Data,
+    },
+    CometdClientBuilder,
+};
+use common::{next_error, MockServer, WAIT_TIMEOUT};
+use core::time::Duration;
+use serde_json::{json, Value};
+use std::sync::{
+    atomic::{AtomicUsize, Ordering},
+    Arc,
+};
+use tokio::{
+    sync::mpsc,
+    time::{sleep, timeout},
+};
+
+#[test]
+fn test_router_extractors() {
+    let data = Data {
+        channel: Some(ChannelId::new("/orders/1").unwrap()),
+        message: Some(json!({ "id": 1 })),
+    };
+
+    assert_eq!(ChannelId::from_data(&data).unwrap(), "/orders/1");
+    assert_eq!(
+        Json::<serde_json::Value>::from_data(&data).unwrap().0,
+        json!({ "id": 1 })
+    );
+    assert!(matches!(
+        Json::<String>::from_data(&data),
+        Err(CometdError::ParseBody(..))
+    ));
+}
+
+#[tokio::test]
+async fn test_route() {
+    let server = MockServer::start().await;
+    let client = CometdClientBuilder::new(&server.url)
+        .build::<Value>()
+        .unwrap();
+    let (tx, mut rx) = mpsc::unbounded_channel();
+
+    let _route = client
+        .route(
+            "/orders/*",
+            move |Json(order): Json<Value>, channel: ChannelId| {
+                let tx = tx.clone();
+                async move {
+                    let _ = tx.send((channel, order));
+                }
+            },
+        )
+        .await
+        .unwrap();
+    server.publish("/orders/1", json!({ "id": 1 }));
+
+    let (channel, order) = timeout(WAIT_TIMEOUT, rx.recv()).await.unwrap().unwrap();
+    assert_eq!(channel, "/orders/1");
+    assert_eq!(order, json!({ "id": 1 }));
+}
+
+#[tokio::test]
+async fn test_route_with_concurrency() {
+    let server = MockServer::start().await;
+    let client = CometdClientBuilder::new(&server.url)
+        .build::<Value>()
+        .unwrap();
+    let running = Arc::new(AtomicUsize::new(0));
+    let max_running = Arc::new(AtomicUsize::new(0));
+    let handled = Arc::new(AtomicUsize::new(0));
+
+    let _route = client
+        .route_with_concurrency("/orders/*", 2, {
+            let running = Arc::clone(&running);
+            let max_running = Arc::clone(&max_running);
+            let handled = Arc::clone(&handled);
+            move |_: ChannelId| {
+                let running = Arc::clone(&running);
+                let max_running = Arc::clone(&max_running);
+                let handled = Arc::clone(&handled);
+                async move {
+                    let now_running = running.fetch_add(1, Ordering::SeqCst) + 1;
+                    max_running.fetch_max(now_running, Ordering::SeqCst);
+                    sleep(Duration::from_millis(100)).await;
+                    running.fetch_sub(1, Ordering::SeqCst);
+                    handled.fetch_add(1, Ordering::SeqCst);
+                }
+            }
+        })
+        .await
+        .unwrap();
+    for id in 0..6 {
+        server.publish(&format!("/orders/{id}"), json!({ "id": id }));
+    }
+
+    server
+        .wait_for(|_| handled.load(Ordering::SeqCst) == 6)
+        .await;
+    assert_eq!(max_running.load(Ordering::SeqCst), 2);
+}
+
+#[tokio::test]
+async fn test_route_handler_failures() {
+    let server = MockServer::start().await;
+    let client = CometdClientBuilder::new(&server.url)
+        .build::<Value>()
+        .unwrap();
+    let mut events = client.rx();
+    let (tx, mut rx) = mpsc::unbounded_channel();
+
+    let _route = client
+        .route("/orders/*", move |channel: ChannelId| {
+            let tx = tx.clone();
+            async move {
+                match channel.as_str() {
+                    "/orders/error" => Err("can't handle order"),
+                    "/orders/panic" => panic!("order handler panicked"),
+                    _ => {
+                        let _ = tx.send(channel);
+                        Ok(())
+                    }
+                }
+            }
+        })
+        .await
+        .unwrap();
+    server.publish("/orders/error", json!({}));
+    server.publish("/orders/panic", json!({}));
+    server.publish("/orders/1", json!({}));
+
+    // failures are reported and don't stop route
+    let channel = timeout(WAIT_TIMEOUT, rx.recv()).await.unwrap().unwrap();
+    assert_eq!(channel, "/orders/1");
+
+    let errors = [next_error(&mut events).await, next_error(&mut events).await];
+    assert!(errors.iter().any(|error| matches!(
+        **error,
+        CometdError::Handler(_, ref error) if error.to_string() == "can't handle order"
+    )));
+    assert!(errors.iter().any(|error| matches!(
+        **error,
+        CometdError::HandlerPanic(_, ref message) if message == "order handler panicked"
+    )));
+}
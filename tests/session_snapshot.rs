@@ -0,0 +1,78 @@
+mod common;
+
+use cometd_client::{types::SessionSnapshot, CometdClientBuilder};
+use common::{next_message, MockServer};
+use serde_json::{json, Value};
+
+#[test]
+fn test_session_snapshot_round_trip() {
+    let json = json!({
+        "client_id": "client0",
+        "cookies": ["session=42; Path=/cometd; Domain=example.com"],
+        "subscriptions": ["/topic0", "/topic1"],
+        "advice": { "reconnect": "retry", "interval": 0, "timeout": 30000 },
+    });
+
+    let snapshot = serde_json::from_value::<SessionSnapshot>(json.clone()).unwrap();
+    assert_eq!(snapshot.client_id.as_deref(), Some("client0"));
+    assert_eq!(serde_json::to_value(&snapshot).unwrap(), json);
+}
+
+#[tokio::test]
+async fn test_resume() {
+    let server = MockServer::start().await;
+    let client = CometdClientBuilder::new(&server.url)
+        .build::<Value>()
+        .unwrap();
+    client.subscribe_batch(&["/topic/a"]).await.unwrap();
+    let snapshot = client.into_snapshot();
+    // connect request of previous client is held until it expires, so it doesn't take messages
+    let hold = server.state().hold;
+    tokio::time::sleep(hold).await;
+
+    let client = CometdClientBuilder::new(&server.url)
+        .resume(snapshot)
+        .build::<Value>()
+        .unwrap();
+    let mut events = client.rx();
+    // publish isn't blocked by connect which checks resumed session
+    client.publish("/topic/b", 1).await.unwrap();
+    server.publish("/topic/a", json!(42));
+
+    assert_eq!(next_message(&mut events).await, Some(json!(42)));
+    assert_eq!(server.received("/meta/handshake").len(), 1);
+    assert!(server.received("/meta/disconnect").is_empty());
+    assert!(server
+        .received("/meta/connect")
+        .iter()
+        .all(|message| message["clientId"] == "client-1"));
+}
+
+#[tokio::test]
+async fn test_rejected_resume() {
+    let server = MockServer::start().await;
+    let client = CometdClientBuilder::new(&server.url)
+        .build::<Value>()
+        .unwrap();
+    client.subscribe_batch(&["/topic/a"]).await.unwrap();
+    let snapshot = client.into_snapshot();
+    // connect request of previous client is held until it expires, so it doesn't take messages
+    let hold = server.state().hold;
+    tokio::time::sleep(hold).await;
+    server.drop_sessions();
+
+    let client = CometdClientBuilder::new(&server.url)
+        .resume(snapshot)
+        .build::<Value>()
+        .unwrap();
+    let mut events = client.rx();
+    // client does handshake and resubscribes
+    server
+        .wait_for(|server| server.subscribers("/topic/a") == 1)
+        .await;
+    server.publish("/topic/a", json!(42));
+
+    assert_eq!(next_message(&mut events).await, Some(json!(42)));
+    assert_eq!(server.received("/meta/handshake").len(), 2);
+    assert_eq!(client.subscriptions(), ["/topic/a"]);
+}
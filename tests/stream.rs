@@ -0,0 +1,56 @@
+mod common;
+
+use cometd_client::{
+    types::{CometdError, Data},
+    CometdClientBuilder,
+};
+use common::{MockServer, WAIT_TIMEOUT};
+use futures_util::StreamExt;
+use serde_json::{json, Value};
+use tokio::time::timeout;
+
+#[tokio::test]
+async fn test_event_receiver_streams() {
+    // nothing listens there, so client stops after handshake error
+    let client = CometdClientBuilder::new(&"http://127.0.0.1:1/".parse().unwrap())
+        .build::<()>()
+        .unwrap();
+
+    let (events, messages, errors) = tokio::join!(
+        client.rx().collect::<Vec<_>>(),
+        client.rx().messages().collect::<Vec<_>>(),
+        client.rx().errors().collect::<Vec<_>>(),
+    );
+
+    assert_eq!(events.len(), 1);
+    assert!(messages.is_empty());
+    assert!(matches!(
+        *errors.as_slice(),
+        [ref error] if matches!(**error, CometdError::Request(..))
+    ));
+}
+
+#[tokio::test]
+async fn test_message_stream() {
+    let server = MockServer::start().await;
+    let client = CometdClientBuilder::new(&server.url)
+        .build::<Value>()
+        .unwrap();
+    let messages = client.rx().messages();
+    client.subscribe_batch(&["/topic/*"]).await.unwrap();
+
+    server.publish("/topic/a", json!(1));
+    server.publish("/topic/b", json!(2));
+    let messages: Vec<Data<Value>> = timeout(WAIT_TIMEOUT, messages.take(2).collect())
+        .await
+        .unwrap();
+
+    let messages = messages
+        .into_iter()
+        .map(|data| (data.channel.unwrap(), data.message.unwrap()))
+        .collect::<Vec<_>>();
+    assert_eq!(messages[0].0, "/topic/a");
+    assert_eq!(messages[0].1, json!(1));
+    assert_eq!(messages[1].0, "/topic/b");
+    assert_eq!(messages[1].1, json!(2));
+}
@@ -0,0 +1,39 @@
+mod common;
+
+use cometd_client::{
+    types::{CometdError, ErrorKind, Reconnect},
+    CometdClientBuilder,
+};
+use common::MockServer;
+
+#[tokio::test]
+async fn test_subscribe_results() {
+    let server = MockServer::start().await;
+    let client = CometdClientBuilder::new(&server.url)
+        .build::<serde_json::Value>()
+        .unwrap();
+
+    let results = client
+        .subscribe_batch(&["/topic/0", "/forbidden", "/topic/1"])
+        .await
+        .unwrap();
+
+    assert!(matches!(
+        *results.as_slice(),
+        [
+            (ref topic0, Ok(())),
+            (ref forbidden, Err(CometdError::WrongResponse(ErrorKind::Subscribe, Reconnect::None, ref error))),
+            (ref topic1, Ok(())),
+        ] if topic0 == "/topic/0" && forbidden == "/forbidden" && error == "403::Forbidden" && topic1 == "/topic/1"
+    ));
+    assert_eq!(client.subscriptions(), ["/topic/0", "/topic/1"]);
+    assert_eq!(server.subscribers("/topic/0"), 1);
+
+    let results = client.unsubscribe(&["/topic/0"]).await.unwrap();
+
+    assert!(matches!(*results.as_slice(), [(ref topic0, Ok(()))] if topic0 == "/topic/0"));
+    assert_eq!(client.subscriptions(), ["/topic/1"]);
+    assert_eq!(server.subscribers("/topic/0"), 0);
+    // client task keeps running after denied subscription
+    assert!(client.publish("/topic/1", 1).await.is_ok());
+}
@@ -0,0 +1,135 @@
+mod common;
+
+use cometd_client::{types::CometdError, CometdClientBuilder};
+use common::{next_error, MockServer, WAIT_TIMEOUT};
+use core::time::Duration;
+use futures_util::FutureExt;
+use serde_json::{json, Value};
+use tokio::time::{sleep, timeout};
+
+#[tokio::test]
+async fn test_subscription_handles() {
+    let server = MockServer::start().await;
+    let client = CometdClientBuilder::new(&server.url)
+        .build::<Value>()
+        .unwrap();
+
+    let mut first = client.subscribe("/topic/a").await.unwrap();
+    let mut second = client.subscribe("/topic/a").await.unwrap();
+    // handles share one server subscription
+    assert_eq!(server.received("/meta/subscribe").len(), 1);
+
+    server.publish("/topic/a", json!(42));
+    for subscription in [&mut first, &mut second] {
+        let data = timeout(WAIT_TIMEOUT, subscription.recv())
+            .await
+            .unwrap()
+            .unwrap()
+            .unwrap();
+        assert_eq!(data.message, Some(json!(42)));
+    }
+
+    drop(first);
+    sleep(Duration::from_millis(200)).await;
+    assert!(server.received("/meta/unsubscribe").is_empty());
+    assert_eq!(server.subscribers("/topic/a"), 1);
+
+    // client unsubscribes after last handle is dropped
+    drop(second);
+    server
+        .wait_for(|server| server.subscribers("/topic/a") == 0)
+        .await;
+    assert_eq!(server.received("/meta/unsubscribe").len(), 1);
+    assert!(client.subscriptions().is_empty());
+
+    assert!(matches!(
+        client.subscribe("/forbidden").await,
+        Err(CometdError::WrongResponse(..))
+    ));
+}
+
+#[tokio::test]
+async fn test_subscription_overflow() {
+    let server = MockServer::start().await;
+    let client = CometdClientBuilder::new(&server.url)
+        .subscription_channel_capacity(1)
+        .build::<Value>()
+        .unwrap();
+    let mut events = client.rx();
+    let mut subscription = client.subscribe("/topic/a").await.unwrap();
+
+    for data in 0..3 {
+        server.publish("/topic/a", json!(data));
+    }
+    let error = next_error(&mut events).await;
+    assert!(matches!(
+        *error,
+        CometdError::SubscriptionOverflow(ref pattern) if pattern.as_str() == "/topic/a"
+    ));
+
+    // handle keeps receiving after overflow
+    let data = subscription.recv().await.unwrap().unwrap();
+    assert_eq!(data.message, Some(json!(0)));
+    server.publish("/topic/a", json!(3));
+    let data = timeout(WAIT_TIMEOUT, subscription.recv())
+        .await
+        .unwrap()
+        .unwrap()
+        .unwrap();
+    assert_eq!(data.message, Some(json!(3)));
+    // overflow is reported once
+    assert!(timeout(Duration::from_millis(10), next_error(&mut events))
+        .await
+        .is_err());
+}
+
+#[tokio::test]
+async fn test_subscription_commands_keep_connect_request() {
+    let server = MockServer::start().await;
+    server.state().hold = Duration::from_secs(1);
+    let client = CometdClientBuilder::new(&server.url)
+        .build::<Value>()
+        .unwrap();
+    let mut subscription = client.subscribe("/topic/a").await.unwrap();
+    server
+        .wait_for(|server| server.received("/meta/connect").len() == 1)
+        .await;
+
+    // subscribing and releasing handles is done while connect request is held by server
+    let other = client.subscribe("/topic/b").await.unwrap();
+    drop(other);
+    server
+        .wait_for(|server| server.subscribers("/topic/b") == 0)
+        .await;
+    assert_eq!(server.received("/meta/connect").len(), 1);
+
+    server.publish("/topic/a", json!(42));
+    let data = timeout(WAIT_TIMEOUT, subscription.recv())
+        .await
+        .unwrap()
+        .unwrap()
+        .unwrap();
+    assert_eq!(data.message, Some(json!(42)));
+}
+
+#[tokio::test]
+async fn test_cancelled_subscribe_releases_handle() {
+    let server = MockServer::start().await;
+    let client = CometdClientBuilder::new(&server.url)
+        .build::<Value>()
+        .unwrap();
+
+    // caller is cancelled after server replied, but before reply was received
+    let mut subscribing = Box::pin(client.subscribe("/topic/a"));
+    assert!((&mut subscribing).now_or_never().is_none());
+    server
+        .wait_for(|server| server.subscribers("/topic/a") == 1)
+        .await;
+    sleep(Duration::from_millis(100)).await;
+    drop(subscribing);
+
+    server
+        .wait_for(|server| server.subscribers("/topic/a") == 0)
+        .await;
+    assert!(client.subscriptions().is_empty());
+}
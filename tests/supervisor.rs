@@ -0,0 +1,106 @@
+mod common;
+
+use cometd_client::{
+    types::{Backoff, CometdClientEvent, CometdError, ErrorKind},
+    CometdClientBuilder,
+};
+use common::{events_until_closed, next_error, MockServer, WAIT_TIMEOUT};
+use core::time::Duration;
+use serde_json::{json, Value};
+use tokio::time::timeout;
+
+#[tokio::test]
+async fn test_supervised_client_survives_errors() {
+    let server = MockServer::start().await;
+    let client = CometdClientBuilder::new(&server.url)
+        .supervised(true)
+        .backoff(Backoff::new(
+            Duration::from_millis(10),
+            Duration::from_millis(50),
+        ))
+        .build::<Value>()
+        .unwrap();
+    let mut events = client.rx();
+    let mut subscription = client.subscribe("/topic/a").await.unwrap();
+
+    server.state().unavailable = true;
+    let error = next_error(&mut events).await;
+    assert!(matches!(*error, CometdError::StatusCode(..)));
+    server.state().unavailable = false;
+
+    // session is lost after unparsable response, so client does handshake and resubscribes
+    server.state().garbage = true;
+    let error = next_error(&mut events).await;
+    assert!(matches!(*error, CometdError::ParseBody(..)));
+    server.state().garbage = false;
+
+    server
+        .wait_for(|server| server.received("/meta/handshake").len() == 2)
+        .await;
+    // previous session is left on server
+    server
+        .wait_for(|server| server.subscribers("/topic/a") == 2)
+        .await;
+    server.publish("/topic/a", json!(42));
+    let data = timeout(WAIT_TIMEOUT, subscription.recv())
+        .await
+        .unwrap()
+        .unwrap()
+        .unwrap();
+    assert_eq!(data.message, Some(json!(42)));
+}
+
+#[tokio::test]
+async fn test_unsupervised_client_stops_on_error() {
+    let server = MockServer::start().await;
+    let client = CometdClientBuilder::new(&server.url)
+        .build::<Value>()
+        .unwrap();
+    let mut events = client.rx();
+    client.subscribe_batch(&["/topic/a"]).await.unwrap();
+
+    server.state().unavailable = true;
+    let error = next_error(&mut events).await;
+    assert!(matches!(*error, CometdError::StatusCode(..)));
+
+    // only disconnect request is done after error
+    let events = events_until_closed(events).await;
+    assert!(matches!(
+        *events.as_slice(),
+        [CometdClientEvent::Error(ref error)]
+            if matches!(**error, CometdError::StatusCode(ErrorKind::Disconnect, ..))
+    ));
+    assert!(matches!(
+        client.publish("/topic/a", 1).await,
+        Err(CometdError::ClientClosed)
+    ));
+}
+
+#[tokio::test]
+async fn test_supervised_client_stops_on_reconnect_none() {
+    let server = MockServer::start().await;
+    {
+        let mut state = server.state();
+        state.connect_denied = true;
+        state.connect_advice = json!({ "reconnect": "none" });
+    }
+    let client = CometdClientBuilder::new(&server.url)
+        .supervised(true)
+        .backoff(Backoff::new(
+            Duration::from_millis(10),
+            Duration::from_millis(50),
+        ))
+        .build::<Value>()
+        .unwrap();
+
+    // client reports failed connect and stops without disconnect request
+    let events = events_until_closed(client.rx()).await;
+    assert!(matches!(
+        *events.as_slice(),
+        [CometdClientEvent::Error(ref error)]
+            if matches!(**error, CometdError::WrongResponse(ErrorKind::Connect, ..))
+    ));
+    assert_eq!(server.received("/meta/handshake").len(), 1);
+    assert_eq!(server.received("/meta/connect").len(), 1);
+    assert!(server.received("/meta/disconnect").is_empty());
+}